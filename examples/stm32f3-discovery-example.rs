@@ -5,6 +5,9 @@ use core::convert::TryInto;
 
 use cortex_m_rt::entry;
 
+use embedded_hal::delay::DelayNs;
+use grove_matrix_led_my9221_rs::{compat::Compat, Playback};
+
 use stm32f3_discovery::stm32f3xx_hal::{self as hal, pac, prelude::*};
 
 #[panic_handler]
@@ -34,7 +37,7 @@ fn main() -> ! {
             .into_af4_open_drain(&mut gpiob.moder, &mut gpiob.otyper, &mut gpiob.afrl);
     scl.internal_pull_up(&mut gpiob.pupdr, true);
     sda.internal_pull_up(&mut gpiob.pupdr, true);
-    let mut i2c = hal::i2c::I2c::new(
+    let i2c = hal::i2c::I2c::new(
         dp.I2C1,
         (scl, sda),
         40.kHz().try_into().unwrap(),
@@ -42,17 +45,23 @@ fn main() -> ! {
         &mut rcc.apb1,
     );
 
-    let mut delay = hal::delay::Delay::new(cp.SYST, clocks);
+    let delay = hal::delay::Delay::new(cp.SYST, clocks);
+
+    // The HAL implements embedded-hal 0.2 traits
+    let mut i2c = Compat(i2c);
+    let mut delay = Compat(delay);
 
-    let mut led_matrix = grove_matrix_led_my9221_rs::My9221LedMatrix::default();
+    let led_matrix = grove_matrix_led_my9221_rs::My9221LedMatrix::default();
 
     let mut emoji = grove_matrix_led_my9221_rs::Emojis::Smiley;
 
     const DELAY: u16 = 5_000u16;
 
     loop {
-        led_matrix.display_emoji(&mut i2c, emoji, DELAY, true);
-        delay.delay_ms(DELAY);
+        led_matrix
+            .display_emoji(&mut i2c, emoji, Playback::Forever)
+            .unwrap();
+        delay.delay_ms(DELAY as u32);
         emoji = emoji.next().expect("There should always be a next emoji");
     }
 }
@@ -0,0 +1,184 @@
+//! Animations played by the driver
+//!
+//! The device holds at most 5 frames sharing a single duration. An
+//! [`Animation`] has any number of [`Keyframe`]s, each with its own duration,
+//! which are sent one after the other by
+//! [`play_animation`](crate::My9221LedMatrix::play_animation) as time
+//! advances.
+//!
+//! # Example
+//!
+//! ```
+//!    use grove_matrix_led_my9221_rs::{
+//!        animation::{Animation, Keyframe, Mode},
+//!        Colors, Frame, Millis,
+//!    };
+//!
+//!    let keyframes = [
+//!        Keyframe::new(Frame::filled(Colors::Red), Millis::new(100)),
+//!        Keyframe::new(Frame::filled(Colors::Green), Millis::new(200)),
+//!        Keyframe::new(Frame::filled(Colors::Blue), Millis::new(300)),
+//!    ];
+//!    let animation = Animation::new(&keyframes, Mode::PingPong);
+//!
+//!    // Red, green, blue, green, red, green...
+//!    let durations: Vec<u16> = animation.steps().take(5).map(|k| k.duration.as_ms()).collect();
+//!    assert_eq!(durations, [100, 200, 300, 200, 100]);
+//!    assert_eq!(animation.cycle_duration(), 800);
+//!    assert_eq!(animation.frame_at(850), Some(&Frame::filled(Colors::Red)));
+//! ```
+
+use crate::{Frame, Millis};
+
+/// A frame and how long it is displayed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Keyframe {
+    /// The frame to display
+    pub frame: Frame,
+    /// How long the frame is displayed
+    pub duration: Millis,
+}
+
+impl Keyframe {
+    /// Create a keyframe
+    pub const fn new(frame: Frame, duration: Millis) -> Self {
+        Self { frame, duration }
+    }
+}
+
+/// How the keyframes of an animation follow each other
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// From the first keyframe to the last one, then stop
+    Once,
+    /// From the first keyframe to the last one, then start over
+    Loop,
+    /// From the first keyframe to the last one and back, then start over
+    PingPong,
+}
+
+/// A sequence of keyframes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Animation<'a> {
+    keyframes: &'a [Keyframe],
+    mode: Mode,
+}
+
+impl<'a> Animation<'a> {
+    /// Create an animation from its keyframes
+    pub const fn new(keyframes: &'a [Keyframe], mode: Mode) -> Self {
+        Self { keyframes, mode }
+    }
+
+    /// The keyframes, in the order they were given
+    pub fn keyframes(&self) -> &'a [Keyframe] {
+        self.keyframes
+    }
+
+    /// How the keyframes follow each other
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// The keyframes in the order they are displayed, endlessly unless the
+    /// mode is [`Mode::Once`]
+    pub fn steps(&self) -> Steps<'a> {
+        Steps {
+            keyframes: self.keyframes,
+            mode: self.mode,
+            index: 0,
+            forward: true,
+            done: self.keyframes.is_empty(),
+        }
+    }
+
+    /// The number of steps before the animation ends or starts over
+    pub fn cycle_len(&self) -> usize {
+        match self.mode {
+            Mode::Once | Mode::Loop => self.keyframes.len(),
+            // The first and last keyframes are not repeated on the way back
+            Mode::PingPong => (2 * self.keyframes.len())
+                .saturating_sub(2)
+                .max(self.keyframes.len()),
+        }
+    }
+
+    /// The duration in milliseconds before the animation ends or starts
+    /// over
+    pub fn cycle_duration(&self) -> u32 {
+        self.steps()
+            .take(self.cycle_len())
+            .map(|keyframe| keyframe.duration.as_ms() as u32)
+            .sum()
+    }
+
+    /// The frame displayed `elapsed` milliseconds after the start of the
+    /// animation, `None` once it has ended
+    pub fn frame_at(&self, elapsed: u32) -> Option<&'a Frame> {
+        let cycle_duration = self.cycle_duration();
+        let mut elapsed = match self.mode {
+            Mode::Once if elapsed >= cycle_duration => return None,
+            Mode::Once => elapsed,
+            _ if cycle_duration == 0 => return self.keyframes.first().map(|k| &k.frame),
+            _ => elapsed % cycle_duration,
+        };
+        for keyframe in self.steps().take(self.cycle_len()) {
+            let duration = keyframe.duration.as_ms() as u32;
+            if elapsed < duration {
+                return Some(&keyframe.frame);
+            }
+            elapsed -= duration;
+        }
+        None
+    }
+}
+
+impl<'a> IntoIterator for &Animation<'a> {
+    type Item = &'a Keyframe;
+    type IntoIter = Steps<'a>;
+
+    fn into_iter(self) -> Steps<'a> {
+        self.steps()
+    }
+}
+
+/// Iterator over the keyframes of an [`Animation`] in the order they are
+/// displayed
+#[derive(Debug, Clone)]
+pub struct Steps<'a> {
+    keyframes: &'a [Keyframe],
+    mode: Mode,
+    index: usize,
+    forward: bool,
+    done: bool,
+}
+
+impl<'a> Iterator for Steps<'a> {
+    type Item = &'a Keyframe;
+
+    fn next(&mut self) -> Option<&'a Keyframe> {
+        if self.done {
+            return None;
+        }
+        let keyframe = &self.keyframes[self.index];
+        let last = self.keyframes.len() - 1;
+        match self.mode {
+            Mode::Once if self.index == last => self.done = true,
+            Mode::Once => self.index += 1,
+            Mode::Loop if self.index == last => self.index = 0,
+            Mode::Loop => self.index += 1,
+            Mode::PingPong if last == 0 => {}
+            Mode::PingPong => {
+                if self.forward && self.index == last || !self.forward && self.index == 0 {
+                    self.forward = !self.forward;
+                }
+                if self.forward {
+                    self.index += 1;
+                } else {
+                    self.index -= 1;
+                }
+            }
+        }
+        Some(keyframe)
+    }
+}
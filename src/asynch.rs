@@ -0,0 +1,1261 @@
+//! Async driver built on the [embedded-hal-async](https://docs.rs/embedded-hal-async)
+//! `I2c` and `DelayNs` traits
+//!
+//! [`My9221LedMatrixAsync`] exposes the same commands as
+//! [`My9221LedMatrix`](crate::My9221LedMatrix), waiting on the bus and on the
+//! delay provider instead of blocking. Both drivers share the execution of
+//! the commands, so they write the same packets.
+
+use embedded_hal_async::{delay::DelayNs, i2c::I2c};
+
+use crate::{
+    animation::Keyframe,
+    discovery,
+    flash::FlashSlot,
+    info::{DeviceInfo, FirmwareVersion},
+    protocol::{self, Command},
+    ColorAnimation, DisplayRotate, Emojis, Frame, Hue, Millis, My9221LedMatrixError, Playback, Rgb,
+    DEFAULT_ADDRESS,
+};
+
+/// The async grove matrix LED driver
+pub struct My9221LedMatrixAsync {
+    address: u8,
+}
+
+impl Default for My9221LedMatrixAsync {
+    /// Create a new instance of the async grove matrix LED driver using the
+    /// default I2C address
+    fn default() -> Self {
+        Self {
+            address: DEFAULT_ADDRESS,
+        }
+    }
+}
+
+/// All the methods available to the user to interact with the device
+impl My9221LedMatrixAsync {
+    /// Create a new instance of the async grove matrix LED driver
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - The I2C address to use (default is 0x65)
+    ///
+    pub fn new(address: u8) -> Self {
+        Self { address }
+    }
+
+    /// The I2C address the driver uses
+    pub fn address(&self) -> u8 {
+        self.address
+    }
+
+    /// Get the device ID information
+    ///
+    /// # Arguments
+    ///
+    /// * `i2c` - The I2C peripheral to use
+    ///
+    /// # Returns
+    ///
+    /// * `Result<u8, My9221LedMatrixError<I2C::Error>>` - Returns the device ID
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal_async::i2c::I2c;
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::My9221LedMatrixAsync;
+    ///    # async fn example<I2C: I2c>(i2c: &mut I2C) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrixAsync::default();
+    ///    let id = led_matrix.get_device_id(i2c).await?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub async fn get_device_id<I2C>(
+        &self,
+        i2c: &mut I2C,
+    ) -> Result<u8, My9221LedMatrixError<I2C::Error>>
+    where
+        I2C: I2c,
+    {
+        let mut buf = [0u8; 1];
+        self.query(i2c, &Command::GetDeviceId, &mut buf).await?;
+        Ok(buf[0])
+    }
+
+    /// Rotate the display
+    ///
+    /// # Arguments
+    ///
+    /// * `i2c` - The I2C peripheral to use
+    /// * `rotate` - The display orientation
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal_async::i2c::I2c;
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::{DisplayRotate, My9221LedMatrixAsync};
+    ///    # async fn example<I2C: I2c>(i2c: &mut I2C) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrixAsync::default();
+    ///    led_matrix.set_led_matrix_rotate(i2c, DisplayRotate::Deg90).await?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub async fn set_led_matrix_rotate<I2C>(
+        &self,
+        i2c: &mut I2C,
+        rotate: DisplayRotate,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
+    where
+        I2C: I2c,
+    {
+        self.write(i2c, &Command::Rotate(rotate)).await
+    }
+
+    /// Stop the display
+    ///
+    /// # Arguments
+    ///
+    /// * `i2c` - The I2C peripheral to use
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal_async::i2c::I2c;
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::My9221LedMatrixAsync;
+    ///    # async fn example<I2C: I2c>(i2c: &mut I2C) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrixAsync::default();
+    ///    led_matrix.stop_display(i2c).await?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub async fn stop_display<I2C>(
+        &self,
+        i2c: &mut I2C,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
+    where
+        I2C: I2c,
+    {
+        self.write(i2c, &Command::DisplayOff).await
+    }
+
+    /// Set the display offset
+    ///
+    /// # Arguments
+    ///
+    /// * `i2c` - The I2C peripheral to use
+    /// * `offset` - The display offset (x, y)
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal_async::i2c::I2c;
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::My9221LedMatrixAsync;
+    ///    # async fn example<I2C: I2c>(i2c: &mut I2C) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrixAsync::default();
+    ///    led_matrix.set_led_matrix_offset(i2c, (1, 2)).await?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub async fn set_led_matrix_offset<I2C>(
+        &self,
+        i2c: &mut I2C,
+        offset: (u8, u8),
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
+    where
+        I2C: I2c,
+    {
+        self.write(i2c, &Command::Offset(offset.0, offset.1)).await
+    }
+
+    /// Turn on the display
+    ///
+    /// # Arguments
+    ///
+    /// * `i2c` - The I2C peripheral to use
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal_async::i2c::I2c;
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::My9221LedMatrixAsync;
+    ///    # async fn example<I2C: I2c>(i2c: &mut I2C) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrixAsync::default();
+    ///    led_matrix.turn_on_led_flash(i2c).await?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub async fn turn_on_led_flash<I2C>(
+        &self,
+        i2c: &mut I2C,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
+    where
+        I2C: I2c,
+    {
+        self.write(i2c, &Command::LedFlashOn).await
+    }
+
+    /// Turn off the display
+    ///
+    /// # Arguments
+    ///
+    /// * `i2c` - The I2C peripheral to use
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal_async::i2c::I2c;
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::My9221LedMatrixAsync;
+    ///    # async fn example<I2C: I2c>(i2c: &mut I2C) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrixAsync::default();
+    ///    led_matrix.turn_off_led_flash(i2c).await?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub async fn turn_off_led_flash<I2C>(
+        &self,
+        i2c: &mut I2C,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
+    where
+        I2C: I2c,
+    {
+        self.write(i2c, &Command::LedFlashOff).await
+    }
+
+    /// Enable auto sleep mode
+    ///
+    /// # Arguments
+    ///
+    /// * `i2c` - The I2C peripheral to use
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal_async::i2c::I2c;
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::My9221LedMatrixAsync;
+    ///    # async fn example<I2C: I2c>(i2c: &mut I2C) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrixAsync::default();
+    ///    led_matrix.enable_auto_sleep(i2c).await?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub async fn enable_auto_sleep<I2C>(
+        &self,
+        i2c: &mut I2C,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
+    where
+        I2C: I2c,
+    {
+        self.write(i2c, &Command::AutoSleepOn).await
+    }
+
+    /// Disable auto sleep mode
+    ///
+    /// # Arguments
+    ///     
+    /// * `i2c` - The I2C peripheral to use
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal_async::i2c::I2c;
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::My9221LedMatrixAsync;
+    ///    # async fn example<I2C: I2c>(i2c: &mut I2C) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrixAsync::default();
+    ///    led_matrix.disable_auto_sleep(i2c).await?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub async fn disable_auto_sleep<I2C>(
+        &self,
+        i2c: &mut I2C,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
+    where
+        I2C: I2c,
+    {
+        self.write(i2c, &Command::AutoSleepOff).await
+    }
+
+    /// Display a bar
+    ///
+    ///
+    /// # Arguments
+    ///
+    /// * `i2c` - The I2C peripheral to use
+    /// * `bar` - The bar to display
+    /// * `playback` - How long to display the bar
+    /// * `color` - The color of the bar, a [`Colors`](crate::Colors) or any
+    ///   color convertible to a [`Hue`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal_async::i2c::I2c;
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::{Colors, My9221LedMatrixAsync, Playback};
+    ///    # async fn example<I2C: I2c>(i2c: &mut I2C) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrixAsync::default();
+    ///    led_matrix.display_bar(i2c, 16, Playback::Forever, Colors::Green).await?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub async fn display_bar<I2C, C>(
+        &self,
+        i2c: &mut I2C,
+        bar: u8,
+        playback: Playback,
+        color: C,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
+    where
+        C: Into<Hue>,
+        I2C: I2c,
+    {
+        let command = Command::DisplayBar {
+            bar: bar.min(32),
+            playback,
+            color: color.into().0,
+        };
+        self.write(i2c, &command).await
+    }
+
+    /// Display an Emoji
+    ///
+    ///
+    /// # Arguments
+    ///
+    /// * `i2c` - The I2C peripheral to use
+    /// * `emoji` - The emoji to display
+    /// * `playback` - How long to display the emoji
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal_async::i2c::I2c;
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::{Emojis, Millis, My9221LedMatrixAsync, Playback};
+    ///    # async fn example<I2C: I2c>(i2c: &mut I2C) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrixAsync::default();
+    ///    led_matrix.display_emoji(i2c, Emojis::Heart, Playback::Once(Millis::new(1_000))).await?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub async fn display_emoji<I2C>(
+        &self,
+        i2c: &mut I2C,
+        emoji: Emojis,
+        playback: Playback,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
+    where
+        I2C: I2c,
+    {
+        let command = Command::DisplayEmoji { emoji, playback };
+        self.write(i2c, &command).await
+    }
+
+    /// Display an number
+    ///
+    ///
+    /// # Arguments
+    ///
+    /// * `i2c` - The I2C peripheral to use
+    /// * `number` - The number to display
+    /// * `playback` - How long to display the number
+    /// * `color` - The color of the number, a [`Colors`](crate::Colors) or any
+    ///   color convertible to a [`Hue`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal_async::i2c::I2c;
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::{Colors, My9221LedMatrixAsync, Playback};
+    ///    # async fn example<I2C: I2c>(i2c: &mut I2C) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrixAsync::default();
+    ///    led_matrix.display_number(i2c, 42, Playback::Forever, Colors::Blue).await?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub async fn display_number<I2C, C>(
+        &self,
+        i2c: &mut I2C,
+        number: u16,
+        playback: Playback,
+        color: C,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
+    where
+        C: Into<Hue>,
+        I2C: I2c,
+    {
+        let command = Command::DisplayNumber {
+            number,
+            playback,
+            color: color.into().0,
+        };
+        self.write(i2c, &command).await
+    }
+
+    /// Display a string
+    ///
+    /// See [`My9221LedMatrix::display_string`](crate::My9221LedMatrix::display_string)
+    /// for the supported characters and how long strings are displayed.
+    ///
+    /// # Arguments
+    ///
+    /// * `i2c` - The I2C peripheral to use
+    /// * `delay` - A delay provider
+    /// * `string` - The string to display
+    /// * `playback` - How long to display the string
+    /// * `color` - The color of the string, a [`Colors`](crate::Colors) or any
+    ///   color convertible to a [`Hue`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal_async::{delay::DelayNs, i2c::I2c};
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::{Colors, My9221LedMatrixAsync, Playback};
+    ///    # async fn example<I2C: I2c, D: DelayNs>(
+    ///    #     i2c: &mut I2C,
+    ///    #     delay: &mut D,
+    ///    # ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrixAsync::default();
+    ///    led_matrix.display_string(i2c, delay, "Hi", Playback::Forever, Colors::Red).await?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub async fn display_string<I2C, D, C>(
+        &self,
+        i2c: &mut I2C,
+        delay: &mut D,
+        string: &str,
+        playback: Playback,
+        color: C,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
+    where
+        C: Into<Hue>,
+        I2C: I2c,
+        D: DelayNs,
+    {
+        if let Some(c) = string.chars().find(|&c| !protocol::is_supported(c)) {
+            return Err(My9221LedMatrixError::UnsupportedChar(c));
+        }
+        self.write_string(i2c, delay, string.chars(), playback, color.into())
+            .await
+    }
+
+    /// Display a string, replacing the characters which aren't supported by
+    /// the firmware font with the closest supported ones
+    ///
+    /// See [`transliterate`](protocol::transliterate).
+    ///
+    /// # Arguments
+    ///
+    /// * `i2c` - The I2C peripheral to use
+    /// * `delay` - A delay provider
+    /// * `string` - The string to display
+    /// * `playback` - How long to display the string
+    /// * `color` - The color of the string, a [`Colors`](crate::Colors) or any
+    ///   color convertible to a [`Hue`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal_async::{delay::DelayNs, i2c::I2c};
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::{Colors, My9221LedMatrixAsync, Playback};
+    ///    # async fn example<I2C: I2c, D: DelayNs>(
+    ///    #     i2c: &mut I2C,
+    ///    #     delay: &mut D,
+    ///    # ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrixAsync::default();
+    ///    led_matrix.display_string_transliterated(i2c, delay, "Héllo", Playback::Forever, Colors::Red).await?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub async fn display_string_transliterated<I2C, D, C>(
+        &self,
+        i2c: &mut I2C,
+        delay: &mut D,
+        string: &str,
+        playback: Playback,
+        color: C,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
+    where
+        C: Into<Hue>,
+        I2C: I2c,
+        D: DelayNs,
+    {
+        let chars = string
+            .chars()
+            .flat_map(|c| protocol::transliterate(c).chars());
+        self.write_string(i2c, delay, chars, playback, color.into())
+            .await
+    }
+
+    /// Display a color block
+    ///
+    /// # Arguments
+    ///
+    /// * `i2c` - The I2C peripheral to use
+    /// * `rgb` - The color to display, an [`Rgb`] or a `u32` in RGB format
+    ///   (0x00RRGGBB)
+    /// * `playback` - How long to display the color block
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal_async::i2c::I2c;
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::{color::Rgb, My9221LedMatrixAsync, Playback};
+    ///    # async fn example<I2C: I2c>(i2c: &mut I2C) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrixAsync::default();
+    ///    led_matrix.display_color_block(i2c, Rgb::new(0xff, 0x80, 0x00), Playback::Forever).await?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub async fn display_color_block<I2C, C>(
+        &self,
+        i2c: &mut I2C,
+        rgb: C,
+        playback: Playback,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
+    where
+        C: Into<Rgb>,
+        I2C: I2c,
+    {
+        let command = Command::DisplayColorBlock {
+            rgb: rgb.into().to_u32(),
+            playback,
+        };
+        self.write(i2c, &command).await
+    }
+
+    /// Display a color bar
+    ///
+    /// # Arguments
+    ///
+    /// * `i2c` - The I2C peripheral to use
+    /// * `bar` - the color bar to display
+    /// * `playback` - How long to display the color bar
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal_async::i2c::I2c;
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::{My9221LedMatrixAsync, Playback};
+    ///    # async fn example<I2C: I2c>(i2c: &mut I2C) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrixAsync::default();
+    ///    led_matrix.display_color_bar(i2c, 16, Playback::Forever).await?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub async fn display_color_bar<I2C>(
+        &self,
+        i2c: &mut I2C,
+        bar: u8,
+        playback: Playback,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
+    where
+        I2C: I2c,
+    {
+        let command = Command::DisplayColorBar { bar, playback };
+        self.write(i2c, &command).await
+    }
+
+    /// Display a color wave
+    ///
+    /// # Arguments
+    ///
+    /// * `i2c` - The I2C peripheral to use
+    /// * `wave` - the color wave to display
+    /// * `playback` - How long to display the wave
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal_async::i2c::I2c;
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::{My9221LedMatrixAsync, Playback};
+    ///    # async fn example<I2C: I2c>(i2c: &mut I2C) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrixAsync::default();
+    ///    led_matrix.display_color_wave(i2c, 0, Playback::Forever).await?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub async fn display_color_wave<I2C>(
+        &self,
+        i2c: &mut I2C,
+        wave: u8,
+        playback: Playback,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
+    where
+        I2C: I2c,
+    {
+        let command = Command::DisplayColorWave { wave, playback };
+        self.write(i2c, &command).await
+    }
+
+    /// Display a color clockwise
+    ///
+    /// # Arguments
+    ///
+    /// * `i2c` - The I2C peripheral to use
+    /// * `clockwise` - If true, the color will be displayed clockwise, if false, anti-clockwise
+    /// * `big` - If true, the color clockwise will be displayed in big size, if false, small size
+    /// * `playback` - How long to display the animation
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal_async::i2c::I2c;
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::{My9221LedMatrixAsync, Playback};
+    ///    # async fn example<I2C: I2c>(i2c: &mut I2C) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrixAsync::default();
+    ///    led_matrix.display_color_clockwise(i2c, true, false, Playback::Forever).await?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub async fn display_color_clockwise<I2C>(
+        &self,
+        i2c: &mut I2C,
+        clockwise: bool,
+        big: bool,
+        playback: Playback,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
+    where
+        I2C: I2c,
+    {
+        let command = Command::DisplayColorClockwise {
+            clockwise,
+            big,
+            playback,
+        };
+        self.write(i2c, &command).await
+    }
+
+    /// Display a color animation
+    ///
+    /// # Arguments
+    ///
+    /// * `i2c` - The I2C peripheral to use
+    /// * `animation` - The animation to display
+    ///   - `ColorAnimation::BigClockWise`
+    ///   - `ColorAnimation::SmallClockWise`
+    ///   - `ColorAnimation::RainbowCycle`
+    ///   - `ColorAnimation::Fire`
+    ///   - `ColorAnimation::Walking`
+    ///   - `ColorAnimation::BrokenHeart`
+    /// * `playback` - How long to display the animation
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal_async::i2c::I2c;
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::{
+    ///        animation::{Animation, Keyframe, Mode}, ColorAnimation, My9221LedMatrixAsync, Playback,
+    ///    };
+    ///    # async fn example<I2C: I2c>(i2c: &mut I2C) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrixAsync::default();
+    ///    led_matrix.display_color_animation(i2c, ColorAnimation::RainbowCycle, Playback::Forever).await?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub async fn display_color_animation<I2C>(
+        &self,
+        i2c: &mut I2C,
+        animation_index: ColorAnimation,
+        playback: Playback,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
+    where
+        I2C: I2c,
+    {
+        let (from_idx, to_idx) = match animation_index {
+            ColorAnimation::BigClockWise => (0, 28),    // big clockwise
+            ColorAnimation::SmallClockWise => (29, 41), // small clockwise
+            ColorAnimation::RainbowCycle => (255, 255), // rainbow cycle
+            ColorAnimation::Fire => (254, 254),         // fire
+            ColorAnimation::Walking => (42, 43),        // walking
+            ColorAnimation::BrokenHeart => (44, 52),    // broken heart
+        };
+        let command = Command::DisplayColorAnimation {
+            from_idx,
+            to_idx,
+            playback,
+        };
+        self.write(i2c, &command).await
+    }
+
+    /// Display the frame
+    ///
+    /// # Arguments
+    ///
+    /// * `i2c` - The I2C peripheral to use
+    /// * `delay` - A delay provider
+    /// * `frames` - The frames to display
+    /// * `playback` - How long to display the frames
+    /// * `frame_number` - The total number of frames
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal_async::{delay::DelayNs, i2c::I2c};
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::{Colors, Frame, My9221LedMatrixAsync, Playback};
+    ///    # async fn example<I2C: I2c, D: DelayNs>(
+    ///    #     i2c: &mut I2C,
+    ///    #     delay: &mut D,
+    ///    # ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrixAsync::default();
+    ///    let frames = [Frame::filled(Colors::Red), Frame::filled(Colors::Blue)];
+    ///    led_matrix.display_frames(i2c, delay, &frames, Playback::Forever, 2).await?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub async fn display_frames<I2C, D>(
+        &self,
+        i2c: &mut I2C,
+        delay: &mut D,
+        frames: &[Frame],
+        playback: Playback,
+        frames_number: u8,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
+    where
+        I2C: I2c,
+        D: DelayNs,
+    {
+        self.write_frames(i2c, delay, frames, playback, frames_number)
+            .await
+    }
+
+    /// Display frames one after the other, each for `frame_time`
+    ///
+    /// Unlike [`display_frames`](Self::display_frames), the number of frames
+    /// is not limited by the device, since each of them is sent when it is
+    /// displayed, for example the frames of a [`TextFrames`](crate::text::TextFrames).
+    ///
+    /// # Arguments
+    ///
+    /// * `i2c` - The I2C peripheral to use
+    /// * `delay` - A delay provider
+    /// * `frames` - The frames to display
+    /// * `frame_time` - How long to display each frame
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal_async::{delay::DelayNs, i2c::I2c};
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::{Colors, Frame, Millis, My9221LedMatrixAsync};
+    ///    # async fn example<I2C: I2c, D: DelayNs>(
+    ///    #     i2c: &mut I2C,
+    ///    #     delay: &mut D,
+    ///    # ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrixAsync::default();
+    ///    let frames = [Frame::filled(Colors::Red), Frame::filled(Colors::Blue)];
+    ///    led_matrix.stream_frames(i2c, delay, frames, Millis::new(100)).await?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub async fn stream_frames<I2C, D, I>(
+        &self,
+        i2c: &mut I2C,
+        delay: &mut D,
+        frames: I,
+        frame_time: Millis,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
+    where
+        I2C: I2c,
+        D: DelayNs,
+        I: IntoIterator<Item = Frame>,
+    {
+        let frames = frames.into_iter().map(|frame| (frame, frame_time));
+        self.write_timed_frames(i2c, delay, frames).await
+    }
+
+    /// Play the keyframes of an animation, each one for its own duration
+    ///
+    /// This completes when the animation ends, which never happens for
+    /// [`Mode::Loop`](crate::animation::Mode::Loop) and
+    /// [`Mode::PingPong`](crate::animation::Mode::PingPong) animations, unless their
+    /// steps are limited, for example with
+    /// `animation.steps().take(n)`.
+    ///
+    /// # Arguments
+    ///
+    /// * `i2c` - The I2C peripheral to use
+    /// * `delay` - A delay provider
+    /// * `steps` - The keyframes to display, an [`Animation`](crate::animation::Animation)
+    ///   or its [`Steps`](crate::animation::Steps)
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal_async::{delay::DelayNs, i2c::I2c};
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::{
+    ///        animation::{Animation, Keyframe, Mode}, Colors, Frame, Millis, My9221LedMatrixAsync,
+    ///    };
+    ///    # async fn example<I2C: I2c, D: DelayNs>(
+    ///    #     i2c: &mut I2C,
+    ///    #     delay: &mut D,
+    ///    # ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrixAsync::default();
+    ///    let keyframes = [
+    ///        Keyframe::new(Frame::filled(Colors::Red), Millis::new(100)),
+    ///        Keyframe::new(Frame::filled(Colors::Blue), Millis::new(500)),
+    ///    ];
+    ///    led_matrix.play_animation(i2c, delay, &Animation::new(&keyframes, Mode::Once)).await?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub async fn play_animation<'a, I2C, D, I>(
+        &self,
+        i2c: &mut I2C,
+        delay: &mut D,
+        steps: I,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
+    where
+        I2C: I2c,
+        D: DelayNs,
+        I: IntoIterator<Item = &'a Keyframe>,
+    {
+        let frames = steps
+            .into_iter()
+            .map(|keyframe| (keyframe.frame, keyframe.duration));
+        self.write_timed_frames(i2c, delay, frames).await
+    }
+
+    /// Store frames to the internal buffer
+    ///
+    /// # Arguments
+    ///
+    /// * `i2c` - The I2C peripheral to use
+    /// * `delay` - A delay provider
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal_async::{delay::DelayNs, i2c::I2c};
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::My9221LedMatrixAsync;
+    ///    # async fn example<I2C: I2c, D: DelayNs>(
+    ///    #     i2c: &mut I2C,
+    ///    #     delay: &mut D,
+    ///    # ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrixAsync::default();
+    ///    led_matrix.store_frames(i2c, delay).await?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub async fn store_frames<I2C, D>(
+        &self,
+        i2c: &mut I2C,
+        delay: &mut D,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
+    where
+        I2C: I2c,
+        D: DelayNs,
+    {
+        self.write_with_delay(i2c, delay, &Command::StoreFlash)
+            .await
+    }
+
+    /// Delete frames from the internal buffer
+    ///
+    /// # Arguments
+    ///
+    /// * `i2c` - The I2C peripheral to use
+    /// * `delay` - A delay provider
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal_async::{delay::DelayNs, i2c::I2c};
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::My9221LedMatrixAsync;
+    ///    # async fn example<I2C: I2c, D: DelayNs>(
+    ///    #     i2c: &mut I2C,
+    ///    #     delay: &mut D,
+    ///    # ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrixAsync::default();
+    ///    led_matrix.delete_frames(i2c, delay).await?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub async fn delete_frames<I2C, D>(
+        &self,
+        i2c: &mut I2C,
+        delay: &mut D,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
+    where
+        I2C: I2c,
+        D: DelayNs,
+    {
+        self.write_with_delay(i2c, delay, &Command::DeleteFlash)
+            .await
+    }
+
+    /// Display frames from the internal buffer
+    ///
+    /// # Arguments
+    ///
+    /// * `i2c` - The I2C peripheral to use
+    /// * `playback` - How long to display the frames
+    /// * `from_idx` - The slot of the first frame to display
+    /// * `to_idx` - The slot of the last frame to display, which can't be
+    ///   before `from_idx`
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal_async::i2c::I2c;
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::{flash::FlashSlot, My9221LedMatrixAsync, Playback};
+    ///    # async fn example<I2C: I2c>(i2c: &mut I2C) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrixAsync::default();
+    ///    led_matrix.display_frames_from_flash(i2c, Playback::Forever, FlashSlot::Slot1, FlashSlot::Slot2).await?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub async fn display_frames_from_flash<I2C>(
+        &self,
+        i2c: &mut I2C,
+        playback: Playback,
+        from_idx: FlashSlot,
+        to_idx: FlashSlot,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
+    where
+        I2C: I2c,
+    {
+        if from_idx > to_idx {
+            return Err(My9221LedMatrixError::InvalidArgument);
+        }
+        let command = Command::DisplayFlash {
+            playback,
+            from_idx: from_idx.index(),
+            to_idx: to_idx.index(),
+        };
+        self.write(i2c, &command).await
+    }
+
+    /// Enable test mode
+    ///
+    /// # Arguments
+    ///
+    /// * `i2c` - The I2C peripheral to use
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal_async::i2c::I2c;
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::My9221LedMatrixAsync;
+    ///    # async fn example<I2C: I2c>(i2c: &mut I2C) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrixAsync::default();
+    ///    led_matrix.enable_test_mode(i2c).await?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub async fn enable_test_mode<I2C>(
+        &self,
+        i2c: &mut I2C,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
+    where
+        I2C: I2c,
+    {
+        self.write(i2c, &Command::TestModeOn).await
+    }
+
+    /// Disable test mode
+    ///
+    /// # Arguments
+    ///
+    /// * `i2c` - The I2C peripheral to use
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal_async::i2c::I2c;
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::My9221LedMatrixAsync;
+    ///    # async fn example<I2C: I2c>(i2c: &mut I2C) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrixAsync::default();
+    ///    led_matrix.disable_test_mode(i2c).await?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub async fn disable_test_mode<I2C>(
+        &self,
+        i2c: &mut I2C,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
+    where
+        I2C: I2c,
+    {
+        self.write(i2c, &Command::TestModeOff).await
+    }
+
+    /// Test getting the version
+    ///
+    /// # Arguments
+    ///
+    /// * `i2c` - The I2C peripheral to use
+    ///
+    /// # Returns
+    ///
+    /// * `Result<u32, My9221LedMatrixError<I2C::Error>>` - Returns the device version
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal_async::i2c::I2c;
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::My9221LedMatrixAsync;
+    ///    # async fn example<I2C: I2c>(i2c: &mut I2C) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrixAsync::default();
+    ///    let version = led_matrix.test_get_version(i2c).await?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub async fn test_get_version<I2C>(
+        &self,
+        i2c: &mut I2C,
+    ) -> Result<u32, My9221LedMatrixError<I2C::Error>>
+    where
+        I2C: I2c,
+    {
+        let mut buf: [u8; 4] = [0; 4];
+        self.query(i2c, &Command::GetVersion, &mut buf).await?;
+        Ok(u32::from_be_bytes(buf))
+    }
+
+    /// Get the device UID
+    ///
+    /// # Arguments
+    ///
+    /// * `i2c` - The I2C peripheral to use
+    ///
+    /// # Returns
+    ///
+    /// * `Result<u8, My9221LedMatrixError<I2C::Error>>` - Returns the device UID
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal_async::i2c::I2c;
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::My9221LedMatrixAsync;
+    ///    # async fn example<I2C: I2c>(i2c: &mut I2C) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrixAsync::default();
+    ///    let uid = led_matrix.get_device_uid(i2c).await?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub async fn get_device_uid<I2C>(
+        &self,
+        i2c: &mut I2C,
+    ) -> Result<u8, My9221LedMatrixError<I2C::Error>>
+    where
+        I2C: I2c,
+    {
+        let mut buf: [u8; 1] = [0; 1];
+        self.query(i2c, &Command::GetDeviceUid, &mut buf).await?;
+        Ok(buf[0])
+    }
+
+    /// Get the device ID, the firmware version and the UID of the device
+    ///
+    /// # Arguments
+    ///
+    /// * `i2c` - The I2C peripheral to use
+    ///
+    /// # Returns
+    ///
+    /// * `Result<DeviceInfo, My9221LedMatrixError<I2C::Error>>` - Returns the
+    ///   device information
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal_async::i2c::I2c;
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::My9221LedMatrixAsync;
+    ///    # async fn example<I2C: I2c>(i2c: &mut I2C) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrixAsync::default();
+    ///    let info = led_matrix.get_device_info(i2c).await?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub async fn get_device_info<I2C>(
+        &self,
+        i2c: &mut I2C,
+    ) -> Result<DeviceInfo, My9221LedMatrixError<I2C::Error>>
+    where
+        I2C: I2c,
+    {
+        Ok(DeviceInfo {
+            device_id: self.get_device_id(i2c).await?,
+            firmware: FirmwareVersion::from(self.test_get_version(i2c).await?),
+            uid: self.get_device_uid(i2c).await?,
+        })
+    }
+
+    /// Set the address of the device
+    ///
+    /// The device isn't checked to answer at its new address, see
+    /// [`readdress`](Self::readdress).
+    ///
+    /// # Arguments
+    ///
+    /// * `i2c` - The I2C peripheral to use
+    /// * `address` - The new address of the device, a 7-bit address which
+    ///   isn't reserved by the I2C specification
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal_async::i2c::I2c;
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::My9221LedMatrixAsync;
+    ///    # async fn example<I2C: I2c>(i2c: &mut I2C) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let mut led_matrix = My9221LedMatrixAsync::default();
+    ///    led_matrix.set_address(i2c, 0x66).await?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub async fn set_address<I2C>(
+        &mut self,
+        i2c: &mut I2C,
+        address: u8,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
+    where
+        I2C: I2c,
+    {
+        if !discovery::is_valid_address(address) {
+            return Err(My9221LedMatrixError::InvalidAddress(address));
+        }
+        self.write(i2c, &Command::SetAddress(address)).await?;
+        self.address = address;
+        Ok(())
+    }
+
+    /// Reset the address of the device
+    ///
+    /// # Arguments
+    ///
+    /// * `i2c` - The I2C peripheral to use
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal_async::i2c::I2c;
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::My9221LedMatrixAsync;
+    ///    # async fn example<I2C: I2c>(i2c: &mut I2C) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let mut led_matrix = My9221LedMatrixAsync::default();
+    ///    led_matrix.reset_address(i2c).await?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub async fn reset_address<I2C>(
+        &mut self,
+        i2c: &mut I2C,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
+    where
+        I2C: I2c,
+    {
+        self.write(i2c, &Command::ResetAddress).await?;
+        self.address = DEFAULT_ADDRESS;
+        Ok(())
+    }
+
+    /// Move the device to another address, checking nothing else answers
+    /// there beforehand and that the device answers there afterwards
+    ///
+    /// If the device doesn't answer at its new address, and doesn't answer
+    /// at its previous one either, its address is reset and the driver uses
+    /// the default address if the device answers there. The outcome of this
+    /// rollback is returned in
+    /// [`AddressNotVerified`](crate::My9221LedMatrixError::AddressNotVerified),
+    /// and the address used afterwards is given by
+    /// [`address`](Self::address).
+    ///
+    /// # Arguments
+    ///
+    /// * `i2c` - The I2C peripheral to use
+    /// * `delay` - A delay provider
+    /// * `address` - The new address of the device, a 7-bit address which
+    ///   isn't reserved by the I2C specification
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal_async::{delay::DelayNs, i2c::I2c};
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::My9221LedMatrixAsync;
+    ///    # async fn example<I2C: I2c, D: DelayNs>(
+    ///    #     i2c: &mut I2C,
+    ///    #     delay: &mut D,
+    ///    # ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let mut led_matrix = My9221LedMatrixAsync::default();
+    ///    led_matrix.readdress(i2c, delay, 0x66).await?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub async fn readdress<I2C, D>(
+        &mut self,
+        i2c: &mut I2C,
+        delay: &mut D,
+        address: u8,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
+    where
+        I2C: I2c,
+        D: DelayNs,
+    {
+        self.move_to(i2c, delay, address).await
+    }
+}
+
+execution!(My9221LedMatrixAsync, [async], [.await]);
@@ -0,0 +1,241 @@
+//! Colors and their conversion to the one byte hue encoding used by the
+//! device for frames, bars, numbers and strings
+//!
+//! Hue bytes from `0x00` to `0xfd` are positions on the color wheel, at full
+//! saturation and brightness, where `0x00` is red and a full turn spans 255
+//! steps. `0xfe` is displayed as white and `0xff` as black (led off).
+//!
+//! Any [`Rgb`] or [`Hsv`] color can be converted to the closest [`Hue`]: dark
+//! colors are encoded as black and unsaturated ones as white, since the
+//! device can only display fully saturated colors at full brightness.
+//!
+//! # Example
+//!
+//! ```
+//!    use grove_matrix_led_my9221_rs::{
+//!        color::{Hsv, Hue, Rgb},
+//!        Colors,
+//!    };
+//!
+//!    assert_eq!(Hue::from(Rgb::new(0xff, 0x00, 0x00)), Hue::from(Colors::Red));
+//!    assert_eq!(Hue::from(Rgb::from(0x0000ff)), Hue(0xaa));
+//!    assert_eq!(Hue::from(Hsv::new(120, 0xff, 0xff)), Hue(0x55));
+//!    assert_eq!(Hue::from(Rgb::new(0xc0, 0xc0, 0xc0)), Hue::WHITE);
+//!    assert_eq!(Rgb::from(Hue(0x55)), Rgb::new(0x00, 0xff, 0x00));
+//! ```
+
+use crate::Colors;
+
+/// Last hue byte which is a position on the color wheel
+const MAX_HUE: u8 = 0xfd;
+
+/// A color as displayed by the device, in its one byte hue encoding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Hue(pub u8);
+
+impl Hue {
+    /// Displayed as white
+    pub const WHITE: Hue = Hue(0xfe);
+    /// Displayed as black (led off)
+    pub const BLACK: Hue = Hue(0xff);
+
+    /// Whether the hue is a position on the color wheel, rather than white
+    /// or black
+    pub const fn is_wheel(&self) -> bool {
+        self.0 <= MAX_HUE
+    }
+}
+
+/// A color with 8 bits per channel
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Rgb {
+    /// Red channel
+    pub r: u8,
+    /// Green channel
+    pub g: u8,
+    /// Blue channel
+    pub b: u8,
+}
+
+impl Rgb {
+    /// Create a color from its channels
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    /// The color in RGB format (0x00RRGGBB)
+    pub const fn to_u32(&self) -> u32 {
+        u32::from_be_bytes([0, self.r, self.g, self.b])
+    }
+}
+
+/// A color as hue, saturation and value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Hsv {
+    /// Position on the color wheel in degrees, red is 0, values of 360 and
+    /// above wrap around
+    pub h: u16,
+    /// Saturation, from gray (0) to fully saturated (255)
+    pub s: u8,
+    /// Value, from black (0) to full brightness (255)
+    pub v: u8,
+}
+
+impl Hsv {
+    /// Create a color from its hue in degrees, saturation and value
+    pub const fn new(h: u16, s: u8, v: u8) -> Self {
+        Self { h, s, v }
+    }
+}
+
+impl From<u8> for Hue {
+    fn from(hue: u8) -> Self {
+        Hue(hue)
+    }
+}
+
+impl From<Hue> for u8 {
+    fn from(hue: Hue) -> Self {
+        hue.0
+    }
+}
+
+impl From<Colors> for Hue {
+    fn from(color: Colors) -> Self {
+        Hue(color as u8)
+    }
+}
+
+impl From<Rgb> for Hue {
+    /// Encode a color to the closest hue byte
+    fn from(rgb: Rgb) -> Self {
+        let max = rgb.r.max(rgb.g).max(rgb.b) as i32;
+        let min = rgb.r.min(rgb.g).min(rgb.b) as i32;
+        let delta = max - min;
+
+        if max < 0x20 {
+            return Hue::BLACK;
+        }
+        if delta * 4 < max {
+            return Hue::WHITE;
+        }
+
+        let hue = (sixths(rgb, delta) * 255 + 3 * delta) / (6 * delta);
+        if hue > MAX_HUE as i32 {
+            // Close enough to a full turn to be displayed as red
+            Hue(0x00)
+        } else {
+            Hue(hue as u8)
+        }
+    }
+}
+
+impl From<Hsv> for Hue {
+    fn from(hsv: Hsv) -> Self {
+        Hue::from(Rgb::from(hsv))
+    }
+}
+
+impl From<Colors> for Rgb {
+    fn from(color: Colors) -> Self {
+        Rgb::from(Hue::from(color))
+    }
+}
+
+impl From<u32> for Rgb {
+    /// Create a color from its RGB format (0x00RRGGBB), the upper byte is
+    /// ignored
+    fn from(rgb: u32) -> Self {
+        let [_, r, g, b] = rgb.to_be_bytes();
+        Self { r, g, b }
+    }
+}
+
+impl From<Rgb> for u32 {
+    fn from(rgb: Rgb) -> Self {
+        rgb.to_u32()
+    }
+}
+
+impl From<Hue> for Rgb {
+    /// Decode a hue byte to the color displayed by the device
+    fn from(hue: Hue) -> Self {
+        match hue {
+            Hue::WHITE => Rgb::new(0xff, 0xff, 0xff),
+            Hue::BLACK => Rgb::new(0x00, 0x00, 0x00),
+            Hue(hue) => {
+                // The wheel is split in six sectors of 255 / 6 steps, in each
+                // of them one channel is rising or falling while the others
+                // are fixed
+                let position = hue as u16 * 6;
+                let rising = (position % 255) as u8;
+                let falling = 0xff - rising;
+                match position / 255 {
+                    0 => Rgb::new(0xff, rising, 0x00),
+                    1 => Rgb::new(falling, 0xff, 0x00),
+                    2 => Rgb::new(0x00, 0xff, rising),
+                    3 => Rgb::new(0x00, falling, 0xff),
+                    4 => Rgb::new(rising, 0x00, 0xff),
+                    _ => Rgb::new(0xff, 0x00, falling),
+                }
+            }
+        }
+    }
+}
+
+impl From<Hsv> for Rgb {
+    fn from(hsv: Hsv) -> Self {
+        let h = (hsv.h % 360) as i32;
+        let v = hsv.v as i32;
+        let chroma = v * hsv.s as i32 / 255;
+        // Second largest channel, rising or falling within the sector
+        let x = chroma * (60 - ((h % 120) - 60).abs()) / 60;
+        let m = v - chroma;
+        let (r, g, b) = match h / 60 {
+            0 => (chroma, x, 0),
+            1 => (x, chroma, 0),
+            2 => (0, chroma, x),
+            3 => (0, x, chroma),
+            4 => (x, 0, chroma),
+            _ => (chroma, 0, x),
+        };
+        Rgb::new((r + m) as u8, (g + m) as u8, (b + m) as u8)
+    }
+}
+
+impl From<Rgb> for Hsv {
+    fn from(rgb: Rgb) -> Self {
+        let max = rgb.r.max(rgb.g).max(rgb.b) as i32;
+        let min = rgb.r.min(rgb.g).min(rgb.b) as i32;
+        let delta = max - min;
+
+        let h = if delta == 0 {
+            0
+        } else {
+            (sixths(rgb, delta) * 60 + delta / 2) / delta % 360
+        };
+        let s = if max == 0 { 0 } else { delta * 255 / max };
+        Hsv::new(h as u16, s as u8, max as u8)
+    }
+}
+
+impl From<Hue> for Hsv {
+    fn from(hue: Hue) -> Self {
+        Hsv::from(Rgb::from(hue))
+    }
+}
+
+/// Position of a color on the wheel in sixths of a turn, scaled by `delta`,
+/// the difference between its largest and smallest channels
+fn sixths(rgb: Rgb, delta: i32) -> i32 {
+    let (r, g, b) = (rgb.r as i32, rgb.g as i32, rgb.b as i32);
+    let max = r.max(g).max(b);
+    if max == r {
+        g - b
+    } else if max == g {
+        2 * delta + b - r
+    } else {
+        4 * delta + r - g
+    }
+    .rem_euclid(6 * delta)
+}
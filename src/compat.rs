@@ -0,0 +1,117 @@
+//! Adapters to use the driver with peripherals implementing the
+//! [embedded-hal 0.2](https://docs.rs/embedded-hal/0.2) traits
+//!
+//! # Example
+//!
+//! ```
+//!    use embedded_hal_mock::eh0::{
+//!        delay::NoopDelay,
+//!        i2c::{Mock as I2cMock, Transaction as I2cTransaction},
+//!    };
+//!    use grove_matrix_led_my9221_rs::{compat::Compat, Colors, Millis, My9221LedMatrix, Playback};
+//!
+//!    // Peripherals implementing the embedded-hal 0.2 traits
+//!    let i2c = I2cMock::new(&[I2cTransaction::write(
+//!        0x65,
+//!        vec![0x04, 0x00, 0x88, 0x13, 0x05, 0x00, b'H', b'e', b'l', b'l', b'o'],
+//!    )]);
+//!    let delay = NoopDelay::new();
+//!
+//!    let mut i2c = Compat(i2c);
+//!    let mut delay = Compat(delay);
+//!
+//!    let led_matrix = My9221LedMatrix::default();
+//!    let playback = Playback::Once(Millis::new(5_000));
+//!    led_matrix.display_string(&mut i2c, &mut delay, "Hello", playback, Colors::Red)?;
+//!
+//!    i2c.into_inner().done();
+//!    # Ok::<(), grove_matrix_led_my9221_rs::My9221LedMatrixError<_>>(())
+//! ```
+
+use embedded_hal::{
+    delay::DelayNs,
+    i2c::{self, ErrorKind, ErrorType, I2c, Operation, SevenBitAddress},
+};
+use embedded_hal_02::blocking::{
+    delay::DelayMs,
+    i2c::{Read, Write, WriteRead},
+};
+
+/// Wrapper implementing the embedded-hal 1.0 traits for an embedded-hal 0.2
+/// I2C peripheral or delay provider
+pub struct Compat<T>(pub T);
+
+impl<T> Compat<T> {
+    /// Release the wrapped peripheral
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+/// An error reported by an embedded-hal 0.2 I2C peripheral
+#[derive(Debug)]
+pub struct Compat02Error<E>(pub E);
+
+impl<E: core::fmt::Debug> i2c::Error for Compat02Error<E> {
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::Other
+    }
+}
+
+impl<T, E> ErrorType for Compat<T>
+where
+    T: Write<Error = E> + Read<Error = E> + WriteRead<Error = E>,
+    E: core::fmt::Debug,
+{
+    type Error = Compat02Error<E>;
+}
+
+impl<T, E> I2c for Compat<T>
+where
+    T: Write<Error = E> + Read<Error = E> + WriteRead<Error = E>,
+    E: core::fmt::Debug,
+{
+    /// Execute the operations one after the other
+    ///
+    /// embedded-hal 0.2 has no transaction support, so each operation is
+    /// issued with its own start and stop conditions.
+    fn transaction(
+        &mut self,
+        address: SevenBitAddress,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        for operation in operations {
+            match operation {
+                Operation::Read(buf) => self.0.read(address, buf),
+                Operation::Write(buf) => self.0.write(address, buf),
+            }
+            .map_err(Compat02Error)?;
+        }
+        Ok(())
+    }
+
+    fn write_read(
+        &mut self,
+        address: SevenBitAddress,
+        write: &[u8],
+        read: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.0
+            .write_read(address, write, read)
+            .map_err(Compat02Error)
+    }
+}
+
+impl<T> DelayNs for Compat<T>
+where
+    T: DelayMs<u32>,
+{
+    /// Wait for at least `ns` nanoseconds, rounded up to the next millisecond
+    fn delay_ns(&mut self, ns: u32) {
+        self.0.delay_ms(ns.div_ceil(1_000_000));
+    }
+
+    fn delay_ms(&mut self, ms: u32) {
+        self.0.delay_ms(ms);
+    }
+}
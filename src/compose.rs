@@ -0,0 +1,261 @@
+//! Compositing of frames
+//!
+//! A source frame is drawn onto a destination one, skipping the leds of the
+//! source having a transparent color or left out by a [`Mask`]. A [`Layers`]
+//! stack flattens several frames into one, ready for
+//! [`display_frames`](crate::My9221LedMatrix::display_frames).
+//!
+//! # Example
+//!
+//! ```
+//!    use grove_matrix_led_my9221_rs::{
+//!        compose::{Layer, Layers, Mask},
+//!        Colors, Frame, Hue,
+//!    };
+//!
+//!    let background = Frame::filled(Colors::Blue);
+//!    let mut sprite = Frame::new();
+//!    sprite.fill_rect(2, 2, 2, 2, Colors::Red);
+//!
+//!    // Black leds of the sprite are transparent
+//!    let mut frame = background;
+//!    frame.overlay(&sprite, Hue::BLACK);
+//!    assert_eq!(frame.get_pixel(2, 2), Some(Hue::from(Colors::Red)));
+//!    assert_eq!(frame.get_pixel(0, 0), Some(Hue::from(Colors::Blue)));
+//!
+//!    let mut layers = Layers::<4>::new();
+//!    layers.push(Layer::new(background)).unwrap();
+//!    layers.push(Layer::new(sprite).with_mask(Mask::rect(0, 0, 3, 8))).unwrap();
+//!    let frame = layers.flatten();
+//!    assert_eq!(frame.get_pixel(2, 2), Some(Hue::from(Colors::Red)));
+//!    assert_eq!(frame.get_pixel(3, 3), Some(Hue::from(Colors::Blue)));
+//! ```
+
+use core::ops::{BitAnd, BitOr, Not};
+
+use crate::{color::Hue, frame, Frame};
+
+/// A set of leds, the led at `(x, y)` being bit `y * 8 + x`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Mask(pub u64);
+
+impl Mask {
+    /// No led
+    pub const NONE: Mask = Mask(0);
+    /// All the leds
+    pub const ALL: Mask = Mask(u64::MAX);
+
+    /// The leds of a rectangle with its top left corner at `(x, y)`,
+    /// clipped to the frame
+    pub fn rect(x: i32, y: i32, width: u8, height: u8) -> Self {
+        let mut mask = Mask::NONE;
+        for py in frame::clipped(y, height, Frame::HEIGHT) {
+            for px in frame::clipped(x, width, Frame::WIDTH) {
+                mask.set(px, py, true);
+            }
+        }
+        mask
+    }
+
+    /// The leds of a frame which are not of the transparent color
+    pub fn opaque(frame: &Frame, transparent: Hue) -> Self {
+        let mut mask = Mask::NONE;
+        for (x, y, hue) in frame.pixels() {
+            mask.set(x, y, hue != transparent);
+        }
+        mask
+    }
+
+    /// Bit of the led at `(x, y)`, if it is in the frame
+    fn bit(x: i32, y: i32) -> Option<u64> {
+        if (0..Frame::WIDTH).contains(&x) && (0..Frame::HEIGHT).contains(&y) {
+            Some(1 << (y * Frame::WIDTH + x))
+        } else {
+            None
+        }
+    }
+
+    /// Whether the led at `(x, y)` is in the set, `false` outside of the
+    /// frame
+    pub fn get(&self, x: i32, y: i32) -> bool {
+        Self::bit(x, y).is_some_and(|bit| self.0 & bit != 0)
+    }
+
+    /// Add or remove the led at `(x, y)`, ignored outside of the frame
+    pub fn set(&mut self, x: i32, y: i32, value: bool) {
+        if let Some(bit) = Self::bit(x, y) {
+            if value {
+                self.0 |= bit;
+            } else {
+                self.0 &= !bit;
+            }
+        }
+    }
+}
+
+impl BitAnd for Mask {
+    type Output = Mask;
+
+    fn bitand(self, rhs: Mask) -> Mask {
+        Mask(self.0 & rhs.0)
+    }
+}
+
+impl BitOr for Mask {
+    type Output = Mask;
+
+    fn bitor(self, rhs: Mask) -> Mask {
+        Mask(self.0 | rhs.0)
+    }
+}
+
+impl Not for Mask {
+    type Output = Mask;
+
+    fn not(self) -> Mask {
+        Mask(!self.0)
+    }
+}
+
+/// Compositing of frames
+impl Frame {
+    /// Draw the leds of `source` which are not of the `transparent` color
+    pub fn overlay<C>(&mut self, source: &Frame, transparent: C)
+    where
+        C: Into<Hue>,
+    {
+        self.overlay_masked(source, Mask::opaque(source, transparent.into()));
+    }
+
+    /// Draw the leds of `source` which are in `mask`
+    pub fn overlay_masked(&mut self, source: &Frame, mask: Mask) {
+        for (i, (hue, &source)) in self.data.iter_mut().zip(&source.data).enumerate() {
+            if mask.0 & (1 << i) != 0 {
+                *hue = source;
+            }
+        }
+    }
+}
+
+/// A frame to composite in a [`Layers`] stack
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Layer {
+    /// The frame drawn by the layer
+    pub frame: Frame,
+    /// The color of the leds which are not drawn, `None` to draw all of them
+    pub transparent: Option<Hue>,
+    /// The leds which may be drawn
+    pub mask: Mask,
+    /// Whether the layer is drawn at all
+    pub visible: bool,
+}
+
+impl Layer {
+    /// Create a visible layer where black leds are transparent
+    pub fn new(frame: Frame) -> Self {
+        Self {
+            frame,
+            transparent: Some(Hue::BLACK),
+            mask: Mask::ALL,
+            visible: true,
+        }
+    }
+
+    /// Set the transparent color, `None` to draw all the leds
+    pub fn with_transparent(mut self, transparent: Option<Hue>) -> Self {
+        self.transparent = transparent;
+        self
+    }
+
+    /// Only draw the leds in `mask`
+    pub fn with_mask(mut self, mask: Mask) -> Self {
+        self.mask = mask;
+        self
+    }
+
+    /// The leds drawn by the layer
+    fn drawn(&self) -> Mask {
+        if !self.visible {
+            return Mask::NONE;
+        }
+        match self.transparent {
+            Some(transparent) => self.mask & Mask::opaque(&self.frame, transparent),
+            None => self.mask,
+        }
+    }
+}
+
+/// A stack of at most `N` layers, the first one being at the bottom
+pub struct Layers<const N: usize> {
+    layers: [Layer; N],
+    len: usize,
+}
+
+impl<const N: usize> Default for Layers<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Layers<N> {
+    /// Create an empty stack
+    pub fn new() -> Self {
+        Self {
+            layers: [Layer::new(Frame::new()); N],
+            len: 0,
+        }
+    }
+
+    /// Add a layer on top of the stack, giving it back if the stack is full
+    pub fn push(&mut self, layer: Layer) -> Result<(), Layer> {
+        if self.len == N {
+            return Err(layer);
+        }
+        self.layers[self.len] = layer;
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Remove the layer on top of the stack
+    pub fn pop(&mut self) -> Option<Layer> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        Some(self.layers[self.len])
+    }
+
+    /// Remove all the layers
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    /// The number of layers
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the stack has no layer
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The layers, from the bottom to the top
+    pub fn layers(&self) -> &[Layer] {
+        &self.layers[..self.len]
+    }
+
+    /// The layers, from the bottom to the top
+    pub fn layers_mut(&mut self) -> &mut [Layer] {
+        &mut self.layers[..self.len]
+    }
+
+    /// Draw the layers from the bottom to the top onto a black frame
+    pub fn flatten(&self) -> Frame {
+        let mut frame = Frame::new();
+        for layer in self.layers() {
+            frame.overlay_masked(&layer.frame, layer.drawn());
+        }
+        frame
+    }
+}
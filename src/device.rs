@@ -0,0 +1,958 @@
+//! Driver owning its I2C bus and delay provider
+//!
+//! [`My9221LedMatrixDevice`] wraps a [`My9221LedMatrix`] together with the
+//! peripherals it uses, so it can be stored as a self-contained peripheral.
+//!
+//! # Example
+//!
+//! ```
+//!    use embedded_hal_mock::eh1::{
+//!        delay::NoopDelay,
+//!        i2c::{Mock as I2cMock, Transaction as I2cTransaction},
+//!    };
+//!    use grove_matrix_led_my9221_rs::{Emojis, My9221LedMatrixDevice, Playback};
+//!
+//!    let i2c = I2cMock::new(&[I2cTransaction::write(0x65, vec![0x02, 0x00, 0x00, 0x00, 0x01])]);
+//!    let mut led_matrix = My9221LedMatrixDevice::new(i2c, NoopDelay::new(), 0x65);
+//!
+//!    led_matrix.display_emoji(Emojis::Smiley, Playback::Forever)?;
+//!
+//!    let (mut i2c, _delay) = led_matrix.release();
+//!    i2c.done();
+//!    # Ok::<(), grove_matrix_led_my9221_rs::My9221LedMatrixError<embedded_hal::i2c::ErrorKind>>(())
+//! ```
+
+use embedded_hal::{delay::DelayNs, i2c::I2c};
+
+use crate::{
+    animation::Keyframe, flash::FlashSlot, info::DeviceInfo, ColorAnimation, DisplayRotate, Emojis,
+    Frame, Hue, Millis, My9221LedMatrix, My9221LedMatrixError, Playback, Rgb,
+};
+
+/// The grove matrix LED driver owning its I2C bus and delay provider
+pub struct My9221LedMatrixDevice<I2C, D> {
+    i2c: I2C,
+    delay: D,
+    matrix: My9221LedMatrix,
+}
+
+/// All the methods available to the user to interact with the device
+impl<I2C, D> My9221LedMatrixDevice<I2C, D>
+where
+    I2C: I2c,
+    D: DelayNs,
+{
+    /// Create a new instance of the grove matrix LED driver
+    ///
+    /// # Arguments
+    ///
+    /// * `i2c` - The I2C peripheral to use
+    /// * `delay` - A delay provider
+    /// * `address` - The I2C address to use (default is 0x65)
+    ///
+    pub fn new(i2c: I2C, delay: D, address: u8) -> Self {
+        Self {
+            i2c,
+            delay,
+            matrix: My9221LedMatrix { address },
+        }
+    }
+
+    /// Release the I2C peripheral and the delay provider
+    pub fn release(self) -> (I2C, D) {
+        (self.i2c, self.delay)
+    }
+
+    /// The I2C address the driver uses
+    pub fn address(&self) -> u8 {
+        self.matrix.address()
+    }
+
+    /// Get the device ID information
+    ///
+    /// # Returns
+    ///
+    /// * `Result<u8, My9221LedMatrixError<I2C::Error>>` - Returns the device ID
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::{delay::DelayNs, i2c::I2c};
+    ///    # use grove_matrix_led_my9221_rs::{My9221LedMatrixDevice, My9221LedMatrixError};
+    ///    # fn example<I2C: I2c, D: DelayNs>(
+    ///    #     led_matrix: &mut My9221LedMatrixDevice<I2C, D>,
+    ///    # ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let id = led_matrix.get_device_id()?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub fn get_device_id(&mut self) -> Result<u8, My9221LedMatrixError<I2C::Error>> {
+        self.matrix.get_device_id(&mut self.i2c)
+    }
+
+    /// Rotate the display
+    ///
+    /// # Arguments
+    ///
+    /// * `rotate` - The display orientation
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::{delay::DelayNs, i2c::I2c};
+    ///    # use grove_matrix_led_my9221_rs::{My9221LedMatrixDevice, My9221LedMatrixError};
+    ///    use grove_matrix_led_my9221_rs::DisplayRotate;
+    ///    # fn example<I2C: I2c, D: DelayNs>(
+    ///    #     led_matrix: &mut My9221LedMatrixDevice<I2C, D>,
+    ///    # ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    led_matrix.set_led_matrix_rotate(DisplayRotate::Deg90)?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub fn set_led_matrix_rotate(
+        &mut self,
+        rotate: DisplayRotate,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+        self.matrix.set_led_matrix_rotate(&mut self.i2c, rotate)
+    }
+
+    /// Stop the display
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::{delay::DelayNs, i2c::I2c};
+    ///    # use grove_matrix_led_my9221_rs::{My9221LedMatrixDevice, My9221LedMatrixError};
+    ///    # fn example<I2C: I2c, D: DelayNs>(
+    ///    #     led_matrix: &mut My9221LedMatrixDevice<I2C, D>,
+    ///    # ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    led_matrix.stop_display()?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub fn stop_display(&mut self) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+        self.matrix.stop_display(&mut self.i2c)
+    }
+
+    /// Set the display offset
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - The display offset (x, y)
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::{delay::DelayNs, i2c::I2c};
+    ///    # use grove_matrix_led_my9221_rs::{My9221LedMatrixDevice, My9221LedMatrixError};
+    ///    # fn example<I2C: I2c, D: DelayNs>(
+    ///    #     led_matrix: &mut My9221LedMatrixDevice<I2C, D>,
+    ///    # ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    led_matrix.set_led_matrix_offset((1, 2))?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub fn set_led_matrix_offset(
+        &mut self,
+        offset: (u8, u8),
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+        self.matrix.set_led_matrix_offset(&mut self.i2c, offset)
+    }
+
+    /// Turn on the display
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::{delay::DelayNs, i2c::I2c};
+    ///    # use grove_matrix_led_my9221_rs::{My9221LedMatrixDevice, My9221LedMatrixError};
+    ///    # fn example<I2C: I2c, D: DelayNs>(
+    ///    #     led_matrix: &mut My9221LedMatrixDevice<I2C, D>,
+    ///    # ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    led_matrix.turn_on_led_flash()?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub fn turn_on_led_flash(&mut self) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+        self.matrix.turn_on_led_flash(&mut self.i2c)
+    }
+
+    /// Turn off the display
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::{delay::DelayNs, i2c::I2c};
+    ///    # use grove_matrix_led_my9221_rs::{My9221LedMatrixDevice, My9221LedMatrixError};
+    ///    # fn example<I2C: I2c, D: DelayNs>(
+    ///    #     led_matrix: &mut My9221LedMatrixDevice<I2C, D>,
+    ///    # ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    led_matrix.turn_off_led_flash()?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub fn turn_off_led_flash(&mut self) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+        self.matrix.turn_off_led_flash(&mut self.i2c)
+    }
+
+    /// Enable auto sleep mode
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::{delay::DelayNs, i2c::I2c};
+    ///    # use grove_matrix_led_my9221_rs::{My9221LedMatrixDevice, My9221LedMatrixError};
+    ///    # fn example<I2C: I2c, D: DelayNs>(
+    ///    #     led_matrix: &mut My9221LedMatrixDevice<I2C, D>,
+    ///    # ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    led_matrix.enable_auto_sleep()?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub fn enable_auto_sleep(&mut self) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+        self.matrix.enable_auto_sleep(&mut self.i2c)
+    }
+
+    /// Disable auto sleep mode
+    ///
+    /// # Arguments
+    ///     
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::{delay::DelayNs, i2c::I2c};
+    ///    # use grove_matrix_led_my9221_rs::{My9221LedMatrixDevice, My9221LedMatrixError};
+    ///    # fn example<I2C: I2c, D: DelayNs>(
+    ///    #     led_matrix: &mut My9221LedMatrixDevice<I2C, D>,
+    ///    # ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    led_matrix.disable_auto_sleep()?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub fn disable_auto_sleep(&mut self) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+        self.matrix.disable_auto_sleep(&mut self.i2c)
+    }
+
+    /// Display a bar
+    ///
+    ///
+    /// # Arguments
+    ///
+    /// * `bar` - The bar to display
+    /// * `playback` - How long to display the bar
+    /// * `color` - The color of the bar, a [`Colors`](crate::Colors) or any
+    ///   color convertible to a [`Hue`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::{delay::DelayNs, i2c::I2c};
+    ///    # use grove_matrix_led_my9221_rs::{My9221LedMatrixDevice, My9221LedMatrixError};
+    ///    use grove_matrix_led_my9221_rs::{Colors, Playback};
+    ///    # fn example<I2C: I2c, D: DelayNs>(
+    ///    #     led_matrix: &mut My9221LedMatrixDevice<I2C, D>,
+    ///    # ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    led_matrix.display_bar(16, Playback::Forever, Colors::Green)?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub fn display_bar<C>(
+        &mut self,
+        bar: u8,
+        playback: Playback,
+        color: C,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
+    where
+        C: Into<Hue>,
+    {
+        self.matrix.display_bar(&mut self.i2c, bar, playback, color)
+    }
+
+    /// Display an Emoji
+    ///
+    ///
+    /// # Arguments
+    ///
+    /// * `emoji` - The emoji to display
+    /// * `playback` - How long to display the emoji
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::{delay::DelayNs, i2c::I2c};
+    ///    # use grove_matrix_led_my9221_rs::{My9221LedMatrixDevice, My9221LedMatrixError};
+    ///    use grove_matrix_led_my9221_rs::{Emojis, Millis, Playback};
+    ///    # fn example<I2C: I2c, D: DelayNs>(
+    ///    #     led_matrix: &mut My9221LedMatrixDevice<I2C, D>,
+    ///    # ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    led_matrix.display_emoji(Emojis::Heart, Playback::Once(Millis::new(1_000)))?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub fn display_emoji(
+        &mut self,
+        emoji: Emojis,
+        playback: Playback,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+        self.matrix.display_emoji(&mut self.i2c, emoji, playback)
+    }
+
+    /// Display an number
+    ///
+    ///
+    /// # Arguments
+    ///
+    /// * `number` - The number to display
+    /// * `playback` - How long to display the number
+    /// * `color` - The color of the number, a [`Colors`](crate::Colors) or any
+    ///   color convertible to a [`Hue`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::{delay::DelayNs, i2c::I2c};
+    ///    # use grove_matrix_led_my9221_rs::{My9221LedMatrixDevice, My9221LedMatrixError};
+    ///    use grove_matrix_led_my9221_rs::{Colors, Playback};
+    ///    # fn example<I2C: I2c, D: DelayNs>(
+    ///    #     led_matrix: &mut My9221LedMatrixDevice<I2C, D>,
+    ///    # ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    led_matrix.display_number(42, Playback::Forever, Colors::Blue)?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub fn display_number<C>(
+        &mut self,
+        number: u16,
+        playback: Playback,
+        color: C,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
+    where
+        C: Into<Hue>,
+    {
+        self.matrix
+            .display_number(&mut self.i2c, number, playback, color)
+    }
+
+    /// Display a string
+    ///
+    /// See [`My9221LedMatrix::display_string`] for the supported characters
+    /// and how long strings are displayed.
+    ///
+    /// # Arguments
+    ///
+    /// * `string` - The string to display
+    /// * `playback` - How long to display the string
+    /// * `color` - The color of the string, a [`Colors`](crate::Colors) or any
+    ///   color convertible to a [`Hue`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::{delay::DelayNs, i2c::I2c};
+    ///    # use grove_matrix_led_my9221_rs::{My9221LedMatrixDevice, My9221LedMatrixError};
+    ///    use grove_matrix_led_my9221_rs::{Colors, Playback};
+    ///    # fn example<I2C: I2c, D: DelayNs>(
+    ///    #     led_matrix: &mut My9221LedMatrixDevice<I2C, D>,
+    ///    # ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    led_matrix.display_string("Hi", Playback::Forever, Colors::Red)?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub fn display_string<C>(
+        &mut self,
+        string: &str,
+        playback: Playback,
+        color: C,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
+    where
+        C: Into<Hue>,
+    {
+        self.matrix
+            .display_string(&mut self.i2c, &mut self.delay, string, playback, color)
+    }
+
+    /// Display a string, replacing the characters which aren't supported by
+    /// the firmware font with the closest supported ones
+    ///
+    /// # Arguments
+    ///
+    /// * `string` - The string to display
+    /// * `playback` - How long to display the string
+    /// * `color` - The color of the string, a [`Colors`](crate::Colors) or any
+    ///   color convertible to a [`Hue`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::{delay::DelayNs, i2c::I2c};
+    ///    # use grove_matrix_led_my9221_rs::{My9221LedMatrixDevice, My9221LedMatrixError};
+    ///    use grove_matrix_led_my9221_rs::{Colors, Playback};
+    ///    # fn example<I2C: I2c, D: DelayNs>(
+    ///    #     led_matrix: &mut My9221LedMatrixDevice<I2C, D>,
+    ///    # ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    led_matrix.display_string_transliterated("Héllo", Playback::Forever, Colors::Red)?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub fn display_string_transliterated<C>(
+        &mut self,
+        string: &str,
+        playback: Playback,
+        color: C,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
+    where
+        C: Into<Hue>,
+    {
+        self.matrix.display_string_transliterated(
+            &mut self.i2c,
+            &mut self.delay,
+            string,
+            playback,
+            color,
+        )
+    }
+
+    /// Display a color block
+    ///
+    /// # Arguments
+    ///
+    /// * `rgb` - The color to display, an [`Rgb`] or a `u32` in RGB format
+    ///   (0x00RRGGBB)
+    /// * `playback` - How long to display the color block
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::{delay::DelayNs, i2c::I2c};
+    ///    # use grove_matrix_led_my9221_rs::{My9221LedMatrixDevice, My9221LedMatrixError};
+    ///    use grove_matrix_led_my9221_rs::{color::Rgb, Playback};
+    ///    # fn example<I2C: I2c, D: DelayNs>(
+    ///    #     led_matrix: &mut My9221LedMatrixDevice<I2C, D>,
+    ///    # ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    led_matrix.display_color_block(Rgb::new(0xff, 0x80, 0x00), Playback::Forever)?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub fn display_color_block<C>(
+        &mut self,
+        rgb: C,
+        playback: Playback,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
+    where
+        C: Into<Rgb>,
+    {
+        self.matrix
+            .display_color_block(&mut self.i2c, rgb, playback)
+    }
+
+    /// Display a color bar
+    ///
+    /// # Arguments
+    ///
+    /// * `bar` - the color bar to display
+    /// * `playback` - How long to display the color bar
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::{delay::DelayNs, i2c::I2c};
+    ///    # use grove_matrix_led_my9221_rs::{My9221LedMatrixDevice, My9221LedMatrixError};
+    ///    use grove_matrix_led_my9221_rs::Playback;
+    ///    # fn example<I2C: I2c, D: DelayNs>(
+    ///    #     led_matrix: &mut My9221LedMatrixDevice<I2C, D>,
+    ///    # ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    led_matrix.display_color_bar(16, Playback::Forever)?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub fn display_color_bar(
+        &mut self,
+        bar: u8,
+        playback: Playback,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+        self.matrix.display_color_bar(&mut self.i2c, bar, playback)
+    }
+
+    /// Display a color wave
+    ///
+    /// # Arguments
+    ///
+    /// * `wave` - the color wave to display
+    /// * `playback` - How long to display the wave
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::{delay::DelayNs, i2c::I2c};
+    ///    # use grove_matrix_led_my9221_rs::{My9221LedMatrixDevice, My9221LedMatrixError};
+    ///    use grove_matrix_led_my9221_rs::Playback;
+    ///    # fn example<I2C: I2c, D: DelayNs>(
+    ///    #     led_matrix: &mut My9221LedMatrixDevice<I2C, D>,
+    ///    # ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    led_matrix.display_color_wave(0, Playback::Forever)?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub fn display_color_wave(
+        &mut self,
+        wave: u8,
+        playback: Playback,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+        self.matrix
+            .display_color_wave(&mut self.i2c, wave, playback)
+    }
+
+    /// Display a color clockwise
+    ///
+    /// # Arguments
+    ///
+    /// * `clockwise` - If true, the color will be displayed clockwise, if false, anti-clockwise
+    /// * `big` - If true, the color clockwise will be displayed in big size, if false, small size
+    /// * `playback` - How long to display the animation
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::{delay::DelayNs, i2c::I2c};
+    ///    # use grove_matrix_led_my9221_rs::{My9221LedMatrixDevice, My9221LedMatrixError};
+    ///    use grove_matrix_led_my9221_rs::Playback;
+    ///    # fn example<I2C: I2c, D: DelayNs>(
+    ///    #     led_matrix: &mut My9221LedMatrixDevice<I2C, D>,
+    ///    # ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    led_matrix.display_color_clockwise(true, false, Playback::Forever)?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub fn display_color_clockwise(
+        &mut self,
+        clockwise: bool,
+        big: bool,
+        playback: Playback,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+        self.matrix
+            .display_color_clockwise(&mut self.i2c, clockwise, big, playback)
+    }
+
+    /// Display a color animation
+    ///
+    /// # Arguments
+    ///
+    /// * `animation` - The animation to display
+    ///   - `ColorAnimation::BigClockWise`
+    ///   - `ColorAnimation::SmallClockWise`
+    ///   - `ColorAnimation::RainbowCycle`
+    ///   - `ColorAnimation::Fire`
+    ///   - `ColorAnimation::Walking`
+    ///   - `ColorAnimation::BrokenHeart`
+    /// * `playback` - How long to display the animation
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::{delay::DelayNs, i2c::I2c};
+    ///    # use grove_matrix_led_my9221_rs::{My9221LedMatrixDevice, My9221LedMatrixError};
+    ///    use grove_matrix_led_my9221_rs::{
+    ///        animation::{Animation, Keyframe, Mode}, ColorAnimation, Playback,
+    ///    };
+    ///    # fn example<I2C: I2c, D: DelayNs>(
+    ///    #     led_matrix: &mut My9221LedMatrixDevice<I2C, D>,
+    ///    # ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    led_matrix.display_color_animation(ColorAnimation::RainbowCycle, Playback::Forever)?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub fn display_color_animation(
+        &mut self,
+        animation_index: ColorAnimation,
+        playback: Playback,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+        self.matrix
+            .display_color_animation(&mut self.i2c, animation_index, playback)
+    }
+
+    /// Display the frame
+    ///
+    /// # Arguments
+    ///
+    /// * `frames` - The frames to display
+    /// * `playback` - How long to display the frames
+    /// * `frame_number` - The total number of frames
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::{delay::DelayNs, i2c::I2c};
+    ///    # use grove_matrix_led_my9221_rs::{My9221LedMatrixDevice, My9221LedMatrixError};
+    ///    use grove_matrix_led_my9221_rs::{Colors, Frame, Playback};
+    ///    # fn example<I2C: I2c, D: DelayNs>(
+    ///    #     led_matrix: &mut My9221LedMatrixDevice<I2C, D>,
+    ///    # ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let frames = [Frame::filled(Colors::Red), Frame::filled(Colors::Blue)];
+    ///    led_matrix.display_frames(&frames, Playback::Forever, 2)?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub fn display_frames(
+        &mut self,
+        frames: &[Frame],
+        playback: Playback,
+        frames_number: u8,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+        self.matrix.display_frames(
+            &mut self.i2c,
+            &mut self.delay,
+            frames,
+            playback,
+            frames_number,
+        )
+    }
+
+    /// Display frames one after the other, each for `frame_time`
+    ///
+    /// See [`My9221LedMatrix::stream_frames`].
+    ///
+    /// # Arguments
+    ///
+    /// * `frames` - The frames to display
+    /// * `frame_time` - How long to display each frame
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::{delay::DelayNs, i2c::I2c};
+    ///    # use grove_matrix_led_my9221_rs::{My9221LedMatrixDevice, My9221LedMatrixError};
+    ///    use grove_matrix_led_my9221_rs::{Colors, Frame, Millis};
+    ///    # fn example<I2C: I2c, D: DelayNs>(
+    ///    #     led_matrix: &mut My9221LedMatrixDevice<I2C, D>,
+    ///    # ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let frames = [Frame::filled(Colors::Red), Frame::filled(Colors::Blue)];
+    ///    led_matrix.stream_frames(frames, Millis::new(100))?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub fn stream_frames<I>(
+        &mut self,
+        frames: I,
+        frame_time: Millis,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
+    where
+        I: IntoIterator<Item = Frame>,
+    {
+        self.matrix
+            .stream_frames(&mut self.i2c, &mut self.delay, frames, frame_time)
+    }
+
+    /// Play the keyframes of an animation, each one for its own duration
+    ///
+    /// See [`My9221LedMatrix::play_animation`].
+    ///
+    /// # Arguments
+    ///
+    /// * `steps` - The keyframes to display, an
+    ///   [`Animation`](crate::animation::Animation) or its
+    ///   [`Steps`](crate::animation::Steps)
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::{delay::DelayNs, i2c::I2c};
+    ///    # use grove_matrix_led_my9221_rs::{My9221LedMatrixDevice, My9221LedMatrixError};
+    ///    use grove_matrix_led_my9221_rs::{
+    ///        animation::{Animation, Keyframe, Mode}, Colors, Frame, Millis,
+    ///    };
+    ///    # fn example<I2C: I2c, D: DelayNs>(
+    ///    #     led_matrix: &mut My9221LedMatrixDevice<I2C, D>,
+    ///    # ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let keyframes = [
+    ///        Keyframe::new(Frame::filled(Colors::Red), Millis::new(100)),
+    ///        Keyframe::new(Frame::filled(Colors::Blue), Millis::new(500)),
+    ///    ];
+    ///    led_matrix.play_animation(&Animation::new(&keyframes, Mode::Once))?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub fn play_animation<'a, I>(
+        &mut self,
+        steps: I,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
+    where
+        I: IntoIterator<Item = &'a Keyframe>,
+    {
+        self.matrix
+            .play_animation(&mut self.i2c, &mut self.delay, steps)
+    }
+
+    /// Store frames to the internal buffer
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::{delay::DelayNs, i2c::I2c};
+    ///    # use grove_matrix_led_my9221_rs::{My9221LedMatrixDevice, My9221LedMatrixError};
+    ///    # fn example<I2C: I2c, D: DelayNs>(
+    ///    #     led_matrix: &mut My9221LedMatrixDevice<I2C, D>,
+    ///    # ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    led_matrix.store_frames()?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub fn store_frames(&mut self) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+        self.matrix.store_frames(&mut self.i2c, &mut self.delay)
+    }
+
+    /// Delete frames from the internal buffer
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::{delay::DelayNs, i2c::I2c};
+    ///    # use grove_matrix_led_my9221_rs::{My9221LedMatrixDevice, My9221LedMatrixError};
+    ///    # fn example<I2C: I2c, D: DelayNs>(
+    ///    #     led_matrix: &mut My9221LedMatrixDevice<I2C, D>,
+    ///    # ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    led_matrix.delete_frames()?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub fn delete_frames(&mut self) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+        self.matrix.delete_frames(&mut self.i2c, &mut self.delay)
+    }
+
+    /// Display frames from the internal buffer
+    ///
+    /// # Arguments
+    ///
+    /// * `playback` - How long to display the frames
+    /// * `from_idx` - The slot of the first frame to display
+    /// * `to_idx` - The slot of the last frame to display, which can't be
+    ///   before `from_idx`
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::{delay::DelayNs, i2c::I2c};
+    ///    # use grove_matrix_led_my9221_rs::{My9221LedMatrixDevice, My9221LedMatrixError};
+    ///    use grove_matrix_led_my9221_rs::{flash::FlashSlot, Playback};
+    ///    # fn example<I2C: I2c, D: DelayNs>(
+    ///    #     led_matrix: &mut My9221LedMatrixDevice<I2C, D>,
+    ///    # ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    led_matrix.display_frames_from_flash(Playback::Forever, FlashSlot::Slot1, FlashSlot::Slot2)?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub fn display_frames_from_flash(
+        &mut self,
+        playback: Playback,
+        from_idx: FlashSlot,
+        to_idx: FlashSlot,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+        self.matrix
+            .display_frames_from_flash(&mut self.i2c, playback, from_idx, to_idx)
+    }
+
+    /// Enable test mode
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::{delay::DelayNs, i2c::I2c};
+    ///    # use grove_matrix_led_my9221_rs::{My9221LedMatrixDevice, My9221LedMatrixError};
+    ///    # fn example<I2C: I2c, D: DelayNs>(
+    ///    #     led_matrix: &mut My9221LedMatrixDevice<I2C, D>,
+    ///    # ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    led_matrix.enable_test_mode()?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub fn enable_test_mode(&mut self) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+        self.matrix.enable_test_mode(&mut self.i2c)
+    }
+
+    /// Disable test mode
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::{delay::DelayNs, i2c::I2c};
+    ///    # use grove_matrix_led_my9221_rs::{My9221LedMatrixDevice, My9221LedMatrixError};
+    ///    # fn example<I2C: I2c, D: DelayNs>(
+    ///    #     led_matrix: &mut My9221LedMatrixDevice<I2C, D>,
+    ///    # ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    led_matrix.disable_test_mode()?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub fn disable_test_mode(&mut self) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+        self.matrix.disable_test_mode(&mut self.i2c)
+    }
+
+    /// Test getting the version
+    ///
+    /// # Returns
+    ///
+    /// * `Result<u32, My9221LedMatrixError<I2C::Error>>` - Returns the device version
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::{delay::DelayNs, i2c::I2c};
+    ///    # use grove_matrix_led_my9221_rs::{My9221LedMatrixDevice, My9221LedMatrixError};
+    ///    # fn example<I2C: I2c, D: DelayNs>(
+    ///    #     led_matrix: &mut My9221LedMatrixDevice<I2C, D>,
+    ///    # ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let version = led_matrix.test_get_version()?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub fn test_get_version(&mut self) -> Result<u32, My9221LedMatrixError<I2C::Error>> {
+        self.matrix.test_get_version(&mut self.i2c)
+    }
+
+    /// Get the device UID
+    ///
+    /// # Returns
+    ///
+    /// * `Result<u8, My9221LedMatrixError<I2C::Error>>` - Returns the device UID
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::{delay::DelayNs, i2c::I2c};
+    ///    # use grove_matrix_led_my9221_rs::{My9221LedMatrixDevice, My9221LedMatrixError};
+    ///    # fn example<I2C: I2c, D: DelayNs>(
+    ///    #     led_matrix: &mut My9221LedMatrixDevice<I2C, D>,
+    ///    # ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let uid = led_matrix.get_device_uid()?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub fn get_device_uid(&mut self) -> Result<u8, My9221LedMatrixError<I2C::Error>> {
+        self.matrix.get_device_uid(&mut self.i2c)
+    }
+
+    /// Get the device ID, the firmware version and the UID of the device
+    ///
+    /// # Returns
+    ///
+    /// * `Result<DeviceInfo, My9221LedMatrixError<I2C::Error>>` - Returns the
+    ///   device information
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::{delay::DelayNs, i2c::I2c};
+    ///    # use grove_matrix_led_my9221_rs::{My9221LedMatrixDevice, My9221LedMatrixError};
+    ///    # fn example<I2C: I2c, D: DelayNs>(
+    ///    #     led_matrix: &mut My9221LedMatrixDevice<I2C, D>,
+    ///    # ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let info = led_matrix.get_device_info()?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub fn get_device_info(&mut self) -> Result<DeviceInfo, My9221LedMatrixError<I2C::Error>> {
+        self.matrix.get_device_info(&mut self.i2c)
+    }
+
+    /// Set the address of the device
+    ///
+    /// The device isn't checked to answer at its new address, see
+    /// [`readdress`](Self::readdress).
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - The new address of the device, a 7-bit address which
+    ///   isn't reserved by the I2C specification
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::{delay::DelayNs, i2c::I2c};
+    ///    # use grove_matrix_led_my9221_rs::{My9221LedMatrixDevice, My9221LedMatrixError};
+    ///    # fn example<I2C: I2c, D: DelayNs>(
+    ///    #     led_matrix: &mut My9221LedMatrixDevice<I2C, D>,
+    ///    # ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    led_matrix.set_address(0x66)?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub fn set_address(&mut self, address: u8) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+        self.matrix.set_address(&mut self.i2c, address)
+    }
+
+    /// Reset the address of the device
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::{delay::DelayNs, i2c::I2c};
+    ///    # use grove_matrix_led_my9221_rs::{My9221LedMatrixDevice, My9221LedMatrixError};
+    ///    # fn example<I2C: I2c, D: DelayNs>(
+    ///    #     led_matrix: &mut My9221LedMatrixDevice<I2C, D>,
+    ///    # ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    led_matrix.reset_address()?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub fn reset_address(&mut self) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+        self.matrix.reset_address(&mut self.i2c)
+    }
+
+    /// Move the device to another address, checking nothing else answers
+    /// there beforehand and that the device answers there afterwards, see
+    /// [`My9221LedMatrix::readdress`]
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - The new address of the device, a 7-bit address which
+    ///   isn't reserved by the I2C specification
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::{delay::DelayNs, i2c::I2c};
+    ///    # use grove_matrix_led_my9221_rs::{My9221LedMatrixDevice, My9221LedMatrixError};
+    ///    # fn example<I2C: I2c, D: DelayNs>(
+    ///    #     led_matrix: &mut My9221LedMatrixDevice<I2C, D>,
+    ///    # ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    led_matrix.readdress(0x66)?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub fn readdress(&mut self, address: u8) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+        self.matrix
+            .readdress(&mut self.i2c, &mut self.delay, address)
+    }
+}
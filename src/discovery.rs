@@ -0,0 +1,176 @@
+//! Discovery of the matrices sharing a bus
+//!
+//! Once matrices are given different addresses with
+//! [`set_address`](crate::My9221LedMatrix::set_address), [`scan`] finds them
+//! again by probing every address which isn't reserved by the I2C
+//! specification.
+//!
+//! A responder is only taken for a matrix if it answers the device ID of the
+//! module and a version. Other devices only receive the device ID query, a
+//! single `0x00` byte followed by a read, and never a display command.
+//!
+//! # Example
+//!
+//! ```
+//!    use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource};
+//!    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+//!    use grove_matrix_led_my9221_rs::{discovery, info::FirmwareVersion};
+//!
+//!    // A matrix at the default address and another device at 0x20
+//!    let expectations: Vec<_> = discovery::ADDRESSES
+//!        .flat_map(|address| match address {
+//!            0x65 => vec![
+//!                I2cTransaction::write_read(0x65, vec![0x00], vec![0x86]),
+//!                I2cTransaction::write_read(0x65, vec![0xe2], vec![0x00, 0x01, 0x00, 0x00]),
+//!                I2cTransaction::write_read(0x65, vec![0xf1], vec![0x2a]),
+//!            ],
+//!            0x20 => vec![I2cTransaction::write_read(0x20, vec![0x00], vec![0x42])],
+//!            _ => vec![I2cTransaction::write_read(address, vec![0x00], vec![0x00])
+//!                .with_error(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address))],
+//!        })
+//!        .collect();
+//!    let mut i2c = I2cMock::new(&expectations);
+//!
+//!    let found = discovery::scan(&mut i2c).collect::<Result<Vec<_>, _>>()?;
+//!
+//!    assert_eq!(found.len(), 1);
+//!    assert_eq!(found[0].matrix.address(), 0x65);
+//!    assert_eq!(found[0].info.firmware, FirmwareVersion::new(1, 0, 0));
+//!    assert_eq!(found[0].info.uid, 0x2a);
+//!
+//!    i2c.done();
+//!    # Ok::<(), grove_matrix_led_my9221_rs::My9221LedMatrixError<embedded_hal::i2c::ErrorKind>>(())
+//! ```
+
+use core::ops::RangeInclusive;
+
+use embedded_hal::i2c::{Error, ErrorKind, I2c};
+
+use crate::{
+    info::{DeviceInfo, FirmwareVersion},
+    My9221LedMatrix, My9221LedMatrixError,
+};
+
+/// The 7-bit addresses which aren't reserved by the I2C specification
+pub const ADDRESSES: RangeInclusive<u8> = 0x08..=0x77;
+
+/// The device ID answered by the matrices, the low byte of the USB vendor ID
+/// of the module
+pub const DEVICE_ID: u8 = 0x86;
+
+/// The version read when nothing drives the bus
+pub(crate) const FLOATING_VERSION: u32 = 0xffff_ffff;
+
+/// Whether `address` is a 7-bit address which isn't reserved by the I2C
+/// specification
+pub fn is_valid_address(address: u8) -> bool {
+    ADDRESSES.contains(&address)
+}
+
+/// A matrix found on the bus
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Discovered {
+    /// The driver of the matrix, using the address it answered at
+    pub matrix: My9221LedMatrix,
+    /// The identification of the matrix
+    pub info: DeviceInfo,
+}
+
+/// Check whether a matrix answers at `address`
+///
+/// # Arguments
+///
+/// * `i2c` - The I2C peripheral to use
+/// * `address` - The I2C address to probe
+///
+/// # Returns
+///
+/// * `Result<Option<Discovered>, My9221LedMatrixError<I2C::Error>>` - Returns
+///   the matrix, or `None` if nothing acknowledges the address or the
+///   responder isn't a matrix
+///
+/// # Example
+///
+/// ```
+///    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+///    use grove_matrix_led_my9221_rs::discovery;
+///
+///    // Another device, which only receives the device ID query
+///    let mut i2c = I2cMock::new(&[I2cTransaction::write_read(0x20, vec![0x00], vec![0x42])]);
+///
+///    assert_eq!(discovery::probe(&mut i2c, 0x20)?, None);
+///
+///    i2c.done();
+///    # Ok::<(), grove_matrix_led_my9221_rs::My9221LedMatrixError<embedded_hal::i2c::ErrorKind>>(())
+/// ```
+///
+pub fn probe<I2C>(
+    i2c: &mut I2C,
+    address: u8,
+) -> Result<Option<Discovered>, My9221LedMatrixError<I2C::Error>>
+where
+    I2C: I2c,
+{
+    let matrix = My9221LedMatrix { address };
+    let Some(version) = matrix.identify(i2c)? else {
+        return Ok(None);
+    };
+    let info = DeviceInfo {
+        device_id: DEVICE_ID,
+        firmware: FirmwareVersion::from(version),
+        uid: matrix.get_device_uid(i2c)?,
+    };
+    Ok(Some(Discovered { matrix, info }))
+}
+
+/// Whether the error is the address not being acknowledged
+pub(crate) fn is_unacknowledged<E>(error: &My9221LedMatrixError<E>) -> bool
+where
+    E: Error,
+{
+    matches!(error, My9221LedMatrixError::I2c(e) if matches!(e.kind(), ErrorKind::NoAcknowledge(_)))
+}
+
+/// Probe all the [`ADDRESSES`] for matrices
+///
+/// Bus errors other than an unacknowledged address are returned by the
+/// iterator, which then goes on with the next address.
+///
+/// # Arguments
+///
+/// * `i2c` - The I2C peripheral to use
+///
+pub fn scan<I2C>(i2c: &mut I2C) -> Scan<'_, I2C>
+where
+    I2C: I2c,
+{
+    Scan {
+        i2c,
+        addresses: ADDRESSES,
+    }
+}
+
+/// Iterator over the matrices found by [`scan`], in the order of their
+/// addresses
+pub struct Scan<'a, I2C> {
+    i2c: &'a mut I2C,
+    addresses: RangeInclusive<u8>,
+}
+
+impl<I2C> Iterator for Scan<'_, I2C>
+where
+    I2C: I2c,
+{
+    type Item = Result<Discovered, My9221LedMatrixError<I2C::Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for address in self.addresses.by_ref() {
+            match probe(self.i2c, address) {
+                Ok(Some(discovered)) => return Some(Ok(discovered)),
+                Ok(None) => {}
+                Err(e) => return Some(Err(e)),
+            }
+        }
+        None
+    }
+}
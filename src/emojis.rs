@@ -1,5 +1,5 @@
 /// Emojis embedded in the device
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Emojis {
     /// A smiley face
     Smiley = 0x00,
@@ -73,9 +73,9 @@ pub enum Emojis {
     Smile = 0x22,
 }
 
-impl Into<u8> for Emojis {
-    fn into(self) -> u8 {
-        self as u8
+impl From<Emojis> for u8 {
+    fn from(emoji: Emojis) -> u8 {
+        emoji as u8
     }
 }
 
@@ -0,0 +1,296 @@
+//! Execution of the commands over the bus, shared by the blocking and async
+//! drivers
+//!
+//! Both drivers turn their arguments into [`Command`](crate::protocol::Command)s
+//! the same way and only differ by the traits of the bus and of the delay
+//! provider, and by waiting on them. The steps taking more than a single
+//! command are written once in `execution!`, which implements them for a
+//! driver:
+//!
+//! * `execution!(My9221LedMatrix, [], [])` for the blocking driver
+//! * `execution!(My9221LedMatrixAsync, [async], [.await])` for the async one
+//!
+//! The `I2c` and `DelayNs` traits are the ones imported by the module
+//! invoking the macro.
+
+/// Implement the execution of the commands for a driver, `async` being given
+/// with `.await` for the async driver
+macro_rules! execution {
+    ($driver:ident, [$($async:tt)?], [$($await:tt)*]) => {
+        /// Execution of the commands over the bus
+        impl $driver {
+            /// Write a command which does not require waiting between its
+            /// packets
+            $($async)? fn write<I2C>(
+                &self,
+                i2c: &mut I2C,
+                command: &$crate::protocol::Command,
+            ) -> Result<(), $crate::My9221LedMatrixError<I2C::Error>>
+            where
+                I2C: I2c,
+            {
+                $crate::My9221LedMatrix::check(command)?;
+                for packet in command.packets() {
+                    i2c.write(self.address, packet.as_bytes())
+                        $($await)*
+                        .map_err($crate::My9221LedMatrixError::I2c)?;
+                }
+                Ok(())
+            }
+
+            /// Write a command, waiting after each packet as required by the
+            /// device
+            $($async)? fn write_with_delay<I2C, D>(
+                &self,
+                i2c: &mut I2C,
+                delay: &mut D,
+                command: &$crate::protocol::Command,
+            ) -> Result<(), $crate::My9221LedMatrixError<I2C::Error>>
+            where
+                I2C: I2c,
+                D: DelayNs,
+            {
+                $crate::My9221LedMatrix::check(command)?;
+                for packet in command.packets() {
+                    i2c.write(self.address, packet.as_bytes())
+                        $($await)*
+                        .map_err($crate::My9221LedMatrixError::I2c)?;
+                    if packet.delay_ms > 0 {
+                        delay.delay_ms(packet.delay_ms)$($await)*;
+                    }
+                }
+                Ok(())
+            }
+
+            /// Write a command and read the answer of the device
+            $($async)? fn query<I2C>(
+                &self,
+                i2c: &mut I2C,
+                command: &$crate::protocol::Command,
+                buf: &mut [u8],
+            ) -> Result<(), $crate::My9221LedMatrixError<I2C::Error>>
+            where
+                I2C: I2c,
+            {
+                $crate::My9221LedMatrix::check(command)?;
+                for packet in command.packets() {
+                    i2c.write_read(self.address, packet.as_bytes(), buf)
+                        $($await)*
+                        .map_err($crate::My9221LedMatrixError::I2c)?;
+                }
+                Ok(())
+            }
+
+            /// Whether any device acknowledges the address, reading a single
+            /// byte
+            $($async)? fn is_acknowledged<I2C>(
+                &self,
+                i2c: &mut I2C,
+            ) -> Result<bool, $crate::My9221LedMatrixError<I2C::Error>>
+            where
+                I2C: I2c,
+            {
+                match i2c.read(self.address, &mut [0])$($await)* {
+                    Ok(()) => Ok(true),
+                    Err(e) => {
+                        let e = $crate::My9221LedMatrixError::I2c(e);
+                        if $crate::discovery::is_unacknowledged(&e) {
+                            Ok(false)
+                        } else {
+                            Err(e)
+                        }
+                    }
+                }
+            }
+
+            /// The version of the device, or `None` if nothing acknowledges
+            /// the address or the responder isn't a matrix
+            pub(crate) $($async)? fn identify<I2C>(
+                &self,
+                i2c: &mut I2C,
+            ) -> Result<Option<u32>, $crate::My9221LedMatrixError<I2C::Error>>
+            where
+                I2C: I2c,
+            {
+                match self.get_device_id(i2c)$($await)* {
+                    Ok($crate::discovery::DEVICE_ID) => {}
+                    Ok(_) => return Ok(None),
+                    Err(e) if $crate::discovery::is_unacknowledged(&e) => return Ok(None),
+                    Err(e) => return Err(e),
+                }
+                let version = self.test_get_version(i2c)$($await)*?;
+                Ok((version != $crate::discovery::FLOATING_VERSION).then_some(version))
+            }
+
+            /// Write the strings made of `chars`, waiting while each one but
+            /// the last is displayed
+            $($async)? fn write_string<I2C, D, I>(
+                &self,
+                i2c: &mut I2C,
+                delay: &mut D,
+                chars: I,
+                playback: $crate::Playback,
+                color: $crate::Hue,
+            ) -> Result<(), $crate::My9221LedMatrixError<I2C::Error>>
+            where
+                I2C: I2c,
+                D: DelayNs,
+                I: Iterator<Item = char> + Clone,
+            {
+                let texts = $crate::protocol::Texts::new(chars, playback)
+                    .ok_or($crate::My9221LedMatrixError::InvalidArgument)?;
+                let mut texts = texts.peekable();
+                while let Some((text, playback)) = texts.next() {
+                    let command = $crate::protocol::Command::DisplayString {
+                        text,
+                        playback,
+                        color: color.0,
+                    };
+                    self.write_with_delay(i2c, delay, &command)$($await)*?;
+                    if let (Some(_), $crate::Playback::Once(duration)) = (texts.peek(), playback) {
+                        delay.delay_ms(duration.as_ms() as u32)$($await)*;
+                    }
+                }
+                Ok(())
+            }
+
+            /// Upload the first `frames_number` frames, at most 5, the last
+            /// one first as the device displays them once the first one is
+            /// received
+            $($async)? fn write_frames<I2C, D>(
+                &self,
+                i2c: &mut I2C,
+                delay: &mut D,
+                frames: &[$crate::Frame],
+                playback: $crate::Playback,
+                frames_number: u8,
+            ) -> Result<(), $crate::My9221LedMatrixError<I2C::Error>>
+            where
+                I2C: I2c,
+                D: DelayNs,
+            {
+                let frames_number = frames_number.min(5);
+                if frames_number == 0 || frames.len() < frames_number as usize {
+                    return Err($crate::My9221LedMatrixError::InvalidArgument);
+                }
+
+                for index in (0..frames_number).rev() {
+                    let command = $crate::protocol::Command::DisplayCustom {
+                        frame: frames[index as usize],
+                        index,
+                        frames_number,
+                        playback,
+                    };
+                    self.write_with_delay(i2c, delay, &command)$($await)*?;
+                }
+                Ok(())
+            }
+
+            /// Display each frame forever, waiting for its duration before
+            /// displaying the next one
+            $($async)? fn write_timed_frames<I2C, D, I>(
+                &self,
+                i2c: &mut I2C,
+                delay: &mut D,
+                frames: I,
+            ) -> Result<(), $crate::My9221LedMatrixError<I2C::Error>>
+            where
+                I2C: I2c,
+                D: DelayNs,
+                I: Iterator<Item = ($crate::Frame, $crate::Millis)>,
+            {
+                for (frame, duration) in frames {
+                    self.write_frames(i2c, delay, &[frame], $crate::Playback::Forever, 1)
+                        $($await)*?;
+                    delay.delay_ms(duration.as_ms() as u32)$($await)*;
+                }
+                Ok(())
+            }
+
+            /// Move the device to another address, see `readdress`
+            $($async)? fn move_to<I2C, D>(
+                &mut self,
+                i2c: &mut I2C,
+                delay: &mut D,
+                address: u8,
+            ) -> Result<(), $crate::My9221LedMatrixError<I2C::Error>>
+            where
+                I2C: I2c,
+                D: DelayNs,
+            {
+                if !$crate::discovery::is_valid_address(address) {
+                    return Err($crate::My9221LedMatrixError::InvalidAddress(address));
+                }
+                if address == self.address {
+                    return Ok(());
+                }
+                let target = Self { address };
+                if target.is_acknowledged(i2c)$($await)*? {
+                    return Err($crate::My9221LedMatrixError::AddressInUse(address));
+                }
+
+                // The device may have received the command even if writing it
+                // failed, its address is checked in any case
+                let command = $crate::protocol::Command::SetAddress(address);
+                let _ = self.write_with_delay(i2c, delay, &command)$($await)*;
+                if let Ok(Some(_)) = target.identify(i2c)$($await)* {
+                    self.address = address;
+                    return Ok(());
+                }
+
+                let rollback = self.roll_back(i2c, delay, &target)$($await)*;
+                Err($crate::My9221LedMatrixError::AddressNotVerified { address, rollback })
+            }
+
+            /// Bring back a device which doesn't answer at the address of
+            /// `target`, returning the address it answers at afterwards, or
+            /// `None` if it answers nowhere
+            ///
+            /// Every step is tried even if a previous one failed, the first
+            /// bus error is returned unless the device answers afterwards.
+            $($async)? fn roll_back<I2C, D>(
+                &mut self,
+                i2c: &mut I2C,
+                delay: &mut D,
+                target: &Self,
+            ) -> Result<Option<u8>, I2C::Error>
+            where
+                I2C: I2c,
+                D: DelayNs,
+            {
+                // The commands are all valid, only the bus may fail
+                let bus_error = |e| match e {
+                    $crate::My9221LedMatrixError::I2c(e) => Some(e),
+                    _ => None,
+                };
+
+                // The device may have ignored the command
+                let mut error = match self.identify(i2c)$($await)* {
+                    Ok(Some(_)) => return Ok(Some(self.address)),
+                    Ok(None) => None,
+                    Err(e) => bus_error(e),
+                };
+                // Otherwise it is brought back to the default address, only
+                // the device acknowledges one of the addresses
+                for matrix in [target, &*self] {
+                    let command = $crate::protocol::Command::ResetAddress;
+                    match matrix.write_with_delay(i2c, delay, &command)$($await)* {
+                        Err(e) if !$crate::discovery::is_unacknowledged(&e) => {
+                            error = error.or(bus_error(e));
+                        }
+                        _ => {}
+                    }
+                }
+                let matrix = Self::default();
+                match matrix.identify(i2c)$($await)* {
+                    Ok(Some(_)) => {
+                        *self = matrix;
+                        Ok(Some(self.address))
+                    }
+                    Ok(None) => error.map_or(Ok(None), Err),
+                    Err(e) => error.or(bus_error(e)).map_or(Ok(None), Err),
+                }
+            }
+        }
+    };
+}
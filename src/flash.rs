@@ -0,0 +1,380 @@
+//! Animations stored in the flash of the device
+//!
+//! The flash holds up to 5 frames, which are replaced all at once by
+//! [`store_frames`](crate::My9221LedMatrix::store_frames) with the frames of
+//! the internal buffer. A [`FlashSlot`] is the index of one of them, and a
+//! [`FlashManager`] keeps track of the named animations stored in the slots,
+//! so several of them can share the flash and be displayed by name.
+//!
+//! The device can't read its flash back, so the manager must be the only
+//! one writing to it, starting from [`clear`](FlashManager::clear).
+//!
+//! # Example
+//!
+//! ```
+//!    use embedded_hal_mock::eh1::{
+//!        delay::NoopDelay,
+//!        i2c::{Mock as I2cMock, Transaction as I2cTransaction},
+//!    };
+//!    use grove_matrix_led_my9221_rs::{
+//!        flash::{FlashManager, FlashSlot},
+//!        protocol::Command,
+//!        Colors, Frame, My9221LedMatrix, Playback,
+//!    };
+//!
+//!    let blink = [Frame::filled(Colors::Red), Frame::new()];
+//!    let wave = [Frame::filled(Colors::Blue), Frame::filled(Colors::Cyan)];
+//!    let all = [blink[0], blink[1], wave[0], wave[1]];
+//!
+//!    let write = |command: Command| {
+//!        command
+//!            .packets()
+//!            .map(|packet| I2cTransaction::write(0x65, packet.as_bytes().to_vec()))
+//!            .collect::<Vec<_>>()
+//!    };
+//!    let upload = |frames: &[Frame]| {
+//!        (0..frames.len())
+//!            .rev()
+//!            .flat_map(|index| {
+//!                write(Command::DisplayCustom {
+//!                    frame: frames[index],
+//!                    index: index as u8,
+//!                    frames_number: frames.len() as u8,
+//!                    playback: Playback::Forever,
+//!                })
+//!            })
+//!            .chain(write(Command::StoreFlash))
+//!            .collect::<Vec<_>>()
+//!    };
+//!    let play = |from_idx, to_idx| {
+//!        write(Command::DisplayFlash {
+//!            playback: Playback::Forever,
+//!            from_idx,
+//!            to_idx,
+//!        })
+//!    };
+//!    let expectations: Vec<_> = [
+//!        write(Command::DeleteFlash),
+//!        upload(&blink),
+//!        play(1, 2),
+//!        // The animations already stored are uploaded again
+//!        upload(&all),
+//!        play(3, 4),
+//!    ]
+//!    .concat();
+//!    let mut i2c = I2cMock::new(&expectations);
+//!    let mut delay = NoopDelay::new();
+//!
+//!    let mut flash = FlashManager::new(My9221LedMatrix::default());
+//!    flash.clear(&mut i2c, &mut delay)?;
+//!    flash.upload(&mut i2c, &mut delay, "blink", &blink, Playback::Forever)?;
+//!    let slots = flash.upload(&mut i2c, &mut delay, "wave", &wave, Playback::Forever)?;
+//!
+//!    assert_eq!(slots, (FlashSlot::Slot3, FlashSlot::Slot4));
+//!    assert_eq!(flash.name(FlashSlot::Slot1), Some("blink"));
+//!    assert_eq!(flash.free_slots(), 1);
+//!
+//!    i2c.done();
+//!    # Ok::<(), grove_matrix_led_my9221_rs::My9221LedMatrixError<embedded_hal::i2c::ErrorKind>>(())
+//! ```
+
+use embedded_hal::{delay::DelayNs, i2c::I2c};
+
+use crate::{Frame, My9221LedMatrix, My9221LedMatrixError, Playback};
+
+/// The number of frames the flash holds
+const SLOTS: usize = 5;
+
+/// The index of a frame stored in flash
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FlashSlot {
+    /// The first frame
+    Slot1 = 1,
+    /// The second frame
+    Slot2 = 2,
+    /// The third frame
+    Slot3 = 3,
+    /// The fourth frame
+    Slot4 = 4,
+    /// The fifth frame
+    Slot5 = 5,
+}
+
+impl FlashSlot {
+    /// All the slots, in order
+    pub const ALL: [FlashSlot; SLOTS] = [
+        FlashSlot::Slot1,
+        FlashSlot::Slot2,
+        FlashSlot::Slot3,
+        FlashSlot::Slot4,
+        FlashSlot::Slot5,
+    ];
+
+    /// The index of the slot, from 1 to 5, as sent to the device
+    pub fn index(self) -> u8 {
+        self as u8
+    }
+
+    /// The position of the slot in the frame buffer, from 0 to 4
+    fn position(self) -> usize {
+        self as usize - 1
+    }
+}
+
+/// The index of a slot, from 1 to 5, returning the index if it's out of
+/// range
+impl TryFrom<u8> for FlashSlot {
+    type Error = u8;
+
+    fn try_from(index: u8) -> Result<Self, Self::Error> {
+        match index {
+            1..=5 => Ok(FlashSlot::ALL[index as usize - 1]),
+            _ => Err(index),
+        }
+    }
+}
+
+/// A frame stored in flash and the name of its animation
+#[derive(Debug, Clone, Copy)]
+struct Stored<'a> {
+    name: &'a str,
+    frame: Frame,
+}
+
+/// The named animations stored in the flash of a device
+///
+/// Animations are stored in consecutive slots, in the order they were
+/// uploaded, and move to the first slots when a previous one is removed.
+pub struct FlashManager<'a> {
+    matrix: My9221LedMatrix,
+    slots: [Option<Stored<'a>>; SLOTS],
+}
+
+impl<'a> FlashManager<'a> {
+    /// Create a manager of the flash of a device, which is assumed to be
+    /// empty
+    pub fn new(matrix: My9221LedMatrix) -> Self {
+        Self {
+            matrix,
+            slots: [None; SLOTS],
+        }
+    }
+
+    /// The driver of the device
+    pub fn matrix(&self) -> &My9221LedMatrix {
+        &self.matrix
+    }
+
+    /// The number of slots which don't hold a frame
+    pub fn free_slots(&self) -> usize {
+        SLOTS - self.len()
+    }
+
+    /// The name of the animation stored in a slot
+    pub fn name(&self, slot: FlashSlot) -> Option<&'a str> {
+        self.slots[slot.position()].map(|stored| stored.name)
+    }
+
+    /// The frame stored in a slot
+    pub fn frame(&self, slot: FlashSlot) -> Option<&Frame> {
+        self.slots[slot.position()]
+            .as_ref()
+            .map(|stored| &stored.frame)
+    }
+
+    /// The first and last slots of an animation
+    pub fn animation(&self, name: &str) -> Option<(FlashSlot, FlashSlot)> {
+        let mut slots = FlashSlot::ALL
+            .into_iter()
+            .filter(|&slot| self.name(slot) == Some(name));
+        let first = slots.next()?;
+        Some((first, slots.next_back().unwrap_or(first)))
+    }
+
+    /// The names of the animations stored, in the order of their slots
+    pub fn animations(&self) -> impl Iterator<Item = &'a str> + '_ {
+        let mut previous = None;
+        self.slots
+            .iter()
+            .flatten()
+            .filter(move |stored| previous.replace(stored.name) != Some(stored.name))
+            .map(|stored| stored.name)
+    }
+
+    /// Delete all the frames stored in flash
+    ///
+    /// # Arguments
+    ///
+    /// * `i2c` - The I2C peripheral to use
+    /// * `delay` - A delay provider
+    ///
+    pub fn clear<I2C, D>(
+        &mut self,
+        i2c: &mut I2C,
+        delay: &mut D,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
+    where
+        I2C: I2c,
+        D: DelayNs,
+    {
+        self.matrix.delete_frames(i2c, delay)?;
+        self.slots = [None; SLOTS];
+        Ok(())
+    }
+
+    /// Store an animation in the free slots following the ones already used,
+    /// then play it back from flash
+    ///
+    /// The playback lets the animation be checked by looking at the matrix,
+    /// it isn't verified: the device can't read its flash back and
+    /// acknowledges the commands whether the frames were stored or not.
+    ///
+    /// As the whole flash is written at once, the frames already stored are
+    /// uploaded again along with the new ones, and displayed while they are.
+    ///
+    /// # Arguments
+    ///
+    /// * `i2c` - The I2C peripheral to use
+    /// * `delay` - A delay provider
+    /// * `name` - The name of the animation, which must not be stored yet
+    /// * `frames` - The frames of the animation
+    /// * `playback` - How long to display the frames
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(FlashSlot, FlashSlot), My9221LedMatrixError<I2C::Error>>` -
+    ///   Returns the first and last slots of the animation, or
+    ///   [`FlashFull`](My9221LedMatrixError::FlashFull) if there aren't
+    ///   enough free slots
+    ///
+    pub fn upload<I2C, D>(
+        &mut self,
+        i2c: &mut I2C,
+        delay: &mut D,
+        name: &'a str,
+        frames: &[Frame],
+        playback: Playback,
+    ) -> Result<(FlashSlot, FlashSlot), My9221LedMatrixError<I2C::Error>>
+    where
+        I2C: I2c,
+        D: DelayNs,
+    {
+        if frames.is_empty() || self.animation(name).is_some() {
+            return Err(My9221LedMatrixError::InvalidArgument);
+        }
+        if frames.len() > self.free_slots() {
+            return Err(My9221LedMatrixError::FlashFull);
+        }
+
+        let mut slots = self.slots;
+        let first = self.len();
+        for (slot, &frame) in slots[first..].iter_mut().zip(frames) {
+            *slot = Some(Stored { name, frame });
+        }
+        self.store(i2c, delay, slots, playback)?;
+
+        let (from_idx, to_idx) = (
+            FlashSlot::ALL[first],
+            FlashSlot::ALL[first + frames.len() - 1],
+        );
+        self.matrix
+            .display_frames_from_flash(i2c, playback, from_idx, to_idx)?;
+        Ok((from_idx, to_idx))
+    }
+
+    /// Display an animation from flash
+    ///
+    /// # Arguments
+    ///
+    /// * `i2c` - The I2C peripheral to use
+    /// * `name` - The name of the animation
+    /// * `playback` - How long to display the frames
+    ///
+    pub fn play<I2C>(
+        &self,
+        i2c: &mut I2C,
+        name: &str,
+        playback: Playback,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
+    where
+        I2C: I2c,
+    {
+        let (from_idx, to_idx) = self
+            .animation(name)
+            .ok_or(My9221LedMatrixError::InvalidArgument)?;
+        self.matrix
+            .display_frames_from_flash(i2c, playback, from_idx, to_idx)
+    }
+
+    /// Remove an animation from flash, moving the following ones to its
+    /// slots
+    ///
+    /// The animations left are displayed while they are uploaded again,
+    /// then the display is stopped.
+    ///
+    /// # Arguments
+    ///
+    /// * `i2c` - The I2C peripheral to use
+    /// * `delay` - A delay provider
+    /// * `name` - The name of the animation
+    ///
+    pub fn remove<I2C, D>(
+        &mut self,
+        i2c: &mut I2C,
+        delay: &mut D,
+        name: &str,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
+    where
+        I2C: I2c,
+        D: DelayNs,
+    {
+        if self.animation(name).is_none() {
+            return Err(My9221LedMatrixError::InvalidArgument);
+        }
+        let mut slots = [None; SLOTS];
+        let kept = self
+            .slots
+            .iter()
+            .flatten()
+            .filter(|stored| stored.name != name);
+        for (slot, &stored) in slots.iter_mut().zip(kept) {
+            *slot = Some(stored);
+        }
+        if slots[0].is_none() {
+            return self.clear(i2c, delay);
+        }
+        self.store(i2c, delay, slots, Playback::Forever)?;
+        self.matrix.stop_display(i2c)
+    }
+
+    /// The number of slots holding a frame
+    fn len(&self) -> usize {
+        self.slots.iter().take_while(|slot| slot.is_some()).count()
+    }
+
+    /// Upload the frames of `slots` to the internal buffer and store them,
+    /// keeping track of them once they are
+    fn store<I2C, D>(
+        &mut self,
+        i2c: &mut I2C,
+        delay: &mut D,
+        slots: [Option<Stored<'a>>; SLOTS],
+        playback: Playback,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
+    where
+        I2C: I2c,
+        D: DelayNs,
+    {
+        let mut frames = [Frame::new(); SLOTS];
+        let mut frames_number = 0;
+        for (frame, stored) in frames.iter_mut().zip(slots.iter().flatten()) {
+            *frame = stored.frame;
+            frames_number += 1;
+        }
+        self.matrix
+            .display_frames(i2c, delay, &frames, playback, frames_number)?;
+        self.matrix.store_frames(i2c, delay)?;
+        self.slots = slots;
+        Ok(())
+    }
+}
@@ -0,0 +1,385 @@
+//! Frames of 8x8 leds
+//!
+//! The led at `(x, y)` is stored at `data[y * 8 + x]`: `x` grows to the
+//! right and `y` grows downwards, `(0, 0)` being the top left led when the
+//! display is not rotated ([`DisplayRotate::Deg0`](crate::DisplayRotate)).
+//!
+//! Drawing is clipped to the frame, so shapes may lie partly outside of it.
+//! Transformations return a new frame, leaving the original one untouched.
+//!
+//! # Example
+//!
+//! ```
+//!    use grove_matrix_led_my9221_rs::{Colors, Frame, Hue};
+//!
+//!    let mut frame = Frame::new();
+//!    frame.draw_rect(0, 0, 8, 8, Colors::Blue);
+//!    frame.draw_line(1, 1, 6, 6, Colors::Red);
+//!    frame.flood_fill(5, 2, Colors::Green);
+//!
+//!    assert_eq!(frame.get_pixel(7, 0), Some(Hue::from(Colors::Blue)));
+//!    assert_eq!(frame.get_pixel(3, 3), Some(Hue::from(Colors::Red)));
+//!    assert_eq!(frame.get_pixel(5, 2), Some(Hue::from(Colors::Green)));
+//!    assert_eq!(frame.get_pixel(2, 5), Some(Hue::BLACK));
+//!    assert_eq!(frame.get_pixel(8, 0), None);
+//! ```
+
+use crate::{color::Hue, DisplayRotate};
+
+/// A struct representing a frame to display
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Frame {
+    pub data: [u8; 64],
+}
+
+impl Default for Frame {
+    /// Create a black frame
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Frame {
+    /// Number of leds in a row
+    pub const WIDTH: i32 = 8;
+    /// Number of leds in a column
+    pub const HEIGHT: i32 = 8;
+
+    /// Create a black frame
+    pub const fn new() -> Self {
+        Self {
+            data: [Hue::BLACK.0; 64],
+        }
+    }
+
+    /// Create a frame with all its leds of the same color
+    pub fn filled<C>(color: C) -> Self
+    where
+        C: Into<Hue>,
+    {
+        Self {
+            data: [color.into().0; 64],
+        }
+    }
+
+    /// Index in `data` of the led at `(x, y)`, if it is in the frame
+    fn index(x: i32, y: i32) -> Option<usize> {
+        if (0..Self::WIDTH).contains(&x) && (0..Self::HEIGHT).contains(&y) {
+            Some((y * Self::WIDTH + x) as usize)
+        } else {
+            None
+        }
+    }
+
+    /// Set the color of the led at `(x, y)`, ignored outside of the frame
+    pub fn set_pixel<C>(&mut self, x: i32, y: i32, color: C)
+    where
+        C: Into<Hue>,
+    {
+        if let Some(i) = Self::index(x, y) {
+            self.data[i] = color.into().0;
+        }
+    }
+
+    /// The color of the led at `(x, y)`, `None` outside of the frame
+    pub fn get_pixel(&self, x: i32, y: i32) -> Option<Hue> {
+        Self::index(x, y).map(|i| Hue(self.data[i]))
+    }
+
+    /// Turn all the leds off
+    pub fn clear(&mut self) {
+        self.fill(Hue::BLACK);
+    }
+
+    /// Set all the leds to the same color
+    pub fn fill<C>(&mut self, color: C)
+    where
+        C: Into<Hue>,
+    {
+        self.data = [color.into().0; 64];
+    }
+
+    /// Iterate over the leds as `(x, y, color)`, row by row from the top
+    /// left one
+    pub fn pixels(&self) -> impl Iterator<Item = (i32, i32, Hue)> + '_ {
+        self.data
+            .iter()
+            .enumerate()
+            .map(|(i, &hue)| (i as i32 % Self::WIDTH, i as i32 / Self::WIDTH, Hue(hue)))
+    }
+
+    /// Draw a line between `(x0, y0)` and `(x1, y1)`, both included
+    pub fn draw_line<C>(&mut self, x0: i32, y0: i32, x1: i32, y1: i32, color: C)
+    where
+        C: Into<Hue>,
+    {
+        let color = color.into();
+        // The longer axis is walked over the leds of the frame only, the
+        // other coordinate being rounded from the slope as Bresenham's
+        // algorithm does, in i128 so that no coordinate overflows
+        let (dx, dy) = (x1 as i128 - x0 as i128, y1 as i128 - y0 as i128);
+        if dx.abs() >= dy.abs() {
+            for x in clipped_between(x0, x1, Self::WIDTH) {
+                let y = y0 as i128 + step(x as i128 - x0 as i128, dy, dx);
+                if let Ok(y) = i32::try_from(y) {
+                    self.set_pixel(x, y, color);
+                }
+            }
+        } else {
+            for y in clipped_between(y0, y1, Self::HEIGHT) {
+                let x = x0 as i128 + step(y as i128 - y0 as i128, dx, dy);
+                if let Ok(x) = i32::try_from(x) {
+                    self.set_pixel(x, y, color);
+                }
+            }
+        }
+    }
+
+    /// Draw the outline of a rectangle with its top left corner at `(x, y)`
+    pub fn draw_rect<C>(&mut self, x: i32, y: i32, width: u8, height: u8, color: C)
+    where
+        C: Into<Hue>,
+    {
+        if width == 0 || height == 0 {
+            return;
+        }
+        let color = color.into();
+        // Past `i32::MAX` the edges are outside of the frame anyway
+        let right = x.saturating_add(width as i32 - 1);
+        let bottom = y.saturating_add(height as i32 - 1);
+        self.draw_line(x, y, right, y, color);
+        self.draw_line(x, bottom, right, bottom, color);
+        self.draw_line(x, y, x, bottom, color);
+        self.draw_line(right, y, right, bottom, color);
+    }
+
+    /// Draw a filled rectangle with its top left corner at `(x, y)`
+    pub fn fill_rect<C>(&mut self, x: i32, y: i32, width: u8, height: u8, color: C)
+    where
+        C: Into<Hue>,
+    {
+        let color = color.into();
+        for py in clipped(y, height, Self::HEIGHT) {
+            for px in clipped(x, width, Self::WIDTH) {
+                self.set_pixel(px, py, color);
+            }
+        }
+    }
+
+    /// Draw the outline of a circle centered on `(x, y)`
+    pub fn draw_circle<C>(&mut self, x: i32, y: i32, radius: u8, color: C)
+    where
+        C: Into<Hue>,
+    {
+        let color = color.into();
+        self.circle(radius, |frame, dx, dy| {
+            for (px, py) in [
+                (dx, dy),
+                (dy, dx),
+                (-dx, dy),
+                (-dy, dx),
+                (dx, -dy),
+                (dy, -dx),
+                (-dx, -dy),
+                (-dy, -dx),
+            ] {
+                frame.set_pixel(x.saturating_add(px), y.saturating_add(py), color);
+            }
+        });
+    }
+
+    /// Draw a filled circle centered on `(x, y)`
+    pub fn fill_circle<C>(&mut self, x: i32, y: i32, radius: u8, color: C)
+    where
+        C: Into<Hue>,
+    {
+        let color = color.into();
+        self.circle(radius, |frame, dx, dy| {
+            // Saturated rows are outside of the frame, and saturated ends
+            // of a row are on the same side of it as the actual ones
+            for (dx, dy) in [(dx, dy), (dx, -dy), (dy, dx), (dy, -dx)] {
+                let y = y.saturating_add(dy);
+                frame.draw_line(x.saturating_sub(dx), y, x.saturating_add(dx), y, color);
+            }
+        });
+    }
+
+    /// Walk the first octant of a circle with the midpoint algorithm,
+    /// calling `plot` with each offset `(dx, dy)` from the center
+    fn circle<F>(&mut self, radius: u8, mut plot: F)
+    where
+        F: FnMut(&mut Self, i32, i32),
+    {
+        let (mut dx, mut dy) = (radius as i32, 0);
+        let mut error = 1 - dx;
+        while dx >= dy {
+            plot(self, dx, dy);
+            dy += 1;
+            if error < 0 {
+                error += 2 * dy + 1;
+            } else {
+                dx -= 1;
+                error += 2 * (dy - dx) + 1;
+            }
+        }
+    }
+
+    /// Replace the color of the area of same colored leds around `(x, y)`,
+    /// leds touching by a corner are not part of the same area
+    pub fn flood_fill<C>(&mut self, x: i32, y: i32, color: C)
+    where
+        C: Into<Hue>,
+    {
+        let color = color.into();
+        let target = match self.get_pixel(x, y) {
+            Some(target) if target != color => target,
+            _ => return,
+        };
+
+        // Each led is pushed at most once, since it is recolored when pushed
+        let mut stack = [(0i32, 0i32); 64];
+        let mut len = 0;
+        self.set_pixel(x, y, color);
+        stack[len] = (x, y);
+        len += 1;
+        while len > 0 {
+            len -= 1;
+            let (x, y) = stack[len];
+            for (nx, ny) in [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)] {
+                if self.get_pixel(nx, ny) == Some(target) {
+                    self.set_pixel(nx, ny, color);
+                    stack[len] = (nx, ny);
+                    len += 1;
+                }
+            }
+        }
+    }
+
+    /// Create a frame where the led at `(x, y)` has the color of the led
+    /// at `source(x, y)`, or is black when the source is outside of the frame
+    fn remap<F>(&self, source: F) -> Frame
+    where
+        F: Fn(i32, i32) -> (i32, i32),
+    {
+        let mut frame = Frame::new();
+        for (i, hue) in frame.data.iter_mut().enumerate() {
+            let (x, y) = (i as i32 % Self::WIDTH, i as i32 / Self::WIDTH);
+            let (sx, sy) = source(x, y);
+            if let Some(color) = self.get_pixel(sx, sy) {
+                *hue = color.0;
+            }
+        }
+        frame
+    }
+
+    /// Rotate the frame clockwise, as the device does with
+    /// [`set_led_matrix_rotate`](crate::My9221LedMatrix::set_led_matrix_rotate)
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    use grove_matrix_led_my9221_rs::{Colors, DisplayRotate, Frame, Hue};
+    ///
+    ///    // A led near the middle of the top row
+    ///    let mut frame = Frame::new();
+    ///    frame.set_pixel(3, 0, Colors::Red);
+    ///
+    ///    let rotated = frame.rotated(DisplayRotate::Deg90);
+    ///    assert_eq!(rotated.get_pixel(7, 3), Some(Hue::from(Colors::Red)));
+    ///    assert_eq!(frame.flipped_vertical().get_pixel(3, 7), Some(Hue::from(Colors::Red)));
+    ///    assert_eq!(frame.shifted(-4, 0).get_pixel(7, 0), Some(Hue::BLACK));
+    ///    assert_eq!(frame.shifted_cyclic(-4, 0).get_pixel(7, 0), Some(Hue::from(Colors::Red)));
+    /// ```
+    pub fn rotated(&self, rotation: DisplayRotate) -> Frame {
+        let (w, h) = (Self::WIDTH - 1, Self::HEIGHT - 1);
+        match rotation {
+            DisplayRotate::Deg0 => *self,
+            DisplayRotate::Deg90 => self.remap(|x, y| (y, h - x)),
+            DisplayRotate::Deg180 => self.remap(|x, y| (w - x, h - y)),
+            DisplayRotate::Deg270 => self.remap(|x, y| (w - y, x)),
+        }
+    }
+
+    /// Mirror the frame left to right
+    pub fn flipped_horizontal(&self) -> Frame {
+        self.remap(|x, y| (Self::WIDTH - 1 - x, y))
+    }
+
+    /// Mirror the frame top to bottom
+    pub fn flipped_vertical(&self) -> Frame {
+        self.remap(|x, y| (x, Self::HEIGHT - 1 - y))
+    }
+
+    /// Mirror the frame along its diagonal from the top left led, swapping
+    /// rows and columns
+    pub fn transposed(&self) -> Frame {
+        self.remap(|x, y| (y, x))
+    }
+
+    /// Move the leds `dx` to the right and `dy` down, negative values
+    /// moving them left and up
+    ///
+    /// Leds moved out of the frame are lost and the uncovered ones are black.
+    pub fn shifted(&self, dx: i32, dy: i32) -> Frame {
+        self.remap(|x, y| (x.wrapping_sub(dx), y.wrapping_sub(dy)))
+    }
+
+    /// Move the leds `dx` to the right and `dy` down, negative values
+    /// moving them left and up
+    ///
+    /// Leds moved out of the frame come back on the opposite side.
+    pub fn shifted_cyclic(&self, dx: i32, dy: i32) -> Frame {
+        self.remap(|x, y| {
+            (
+                (x - dx % Self::WIDTH).rem_euclid(Self::WIDTH),
+                (y - dy % Self::HEIGHT).rem_euclid(Self::HEIGHT),
+            )
+        })
+    }
+
+    /// Replace the color of each led by the result of `f`
+    pub fn map<F>(&self, mut f: F) -> Frame
+    where
+        F: FnMut(Hue) -> Hue,
+    {
+        let mut frame = *self;
+        for hue in frame.data.iter_mut() {
+            *hue = f(Hue(*hue)).0;
+        }
+        frame
+    }
+
+    /// Replace each color by its opposite on the color wheel, and swap
+    /// white and black
+    pub fn inverted(&self) -> Frame {
+        self.map(|hue| match hue {
+            Hue::WHITE => Hue::BLACK,
+            Hue::BLACK => Hue::WHITE,
+            // A full turn of the wheel spans 255 steps
+            Hue(hue) => Hue(((hue as u16 + 128) % 255) as u8),
+        })
+    }
+}
+
+/// The coordinates from `start` to `start + len`, excluded, which are in
+/// `0..size`
+pub(crate) fn clipped(start: i32, len: u8, size: i32) -> core::ops::Range<i32> {
+    start.clamp(0, size)..start.saturating_add(len as i32).clamp(0, size)
+}
+
+/// The coordinates between `a` and `b`, both included, which are in
+/// `0..size`
+fn clipped_between(a: i32, b: i32, size: i32) -> core::ops::RangeInclusive<i32> {
+    a.min(b).max(0)..=a.max(b).min(size - 1)
+}
+
+/// The offset along the shorter axis of a line after `along` steps on its
+/// longer axis, for a line moving by `short` and `long` on each axis,
+/// rounded half away from the start as Bresenham's algorithm does
+fn step(along: i128, short: i128, long: i128) -> i128 {
+    if long == 0 {
+        return 0;
+    }
+    let offset = along * short * long.signum();
+    offset.signum() * ((2 * offset.abs() + long.abs()) / (2 * long.abs()))
+}
@@ -0,0 +1,99 @@
+//! [embedded-graphics](https://docs.rs/embedded-graphics) support
+//!
+//! A [`Frame`] can be used as a [`DrawTarget`] to draw text, shapes and images
+//! before sending it to the device with
+//! [`display_frames`](crate::My9221LedMatrix::display_frames).
+//!
+//! Points use the same coordinates as [`Frame::set_pixel`], colors are
+//! converted to the closest hue byte supported by the device.
+//!
+//! The inherent [`Frame::clear`] turns all the leds off and takes precedence
+//! over [`DrawTarget::clear`], which must be called as
+//! `DrawTarget::clear(&mut frame, color)`.
+//!
+//! A [`TiledDisplay`] is also a [`DrawTarget`], spanning all its tiles.
+
+use core::convert::Infallible;
+
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::{OriginDimensions, Size},
+    pixelcolor::{Rgb888, RgbColor},
+    Pixel,
+};
+
+use crate::{
+    color::{Hue, Rgb},
+    tiling::TiledDisplay,
+    Frame,
+};
+
+impl OriginDimensions for Frame {
+    fn size(&self) -> Size {
+        Size::new(8, 8)
+    }
+}
+
+impl DrawTarget for Frame {
+    type Color = Rgb888;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, rgb) in pixels {
+            self.set_pixel(point.x, point.y, Rgb::from(rgb));
+        }
+        Ok(())
+    }
+
+    fn clear(&mut self, rgb: Self::Color) -> Result<(), Self::Error> {
+        self.fill(Rgb::from(rgb));
+        Ok(())
+    }
+}
+
+impl<const N: usize> OriginDimensions for TiledDisplay<N> {
+    fn size(&self) -> Size {
+        Size::new(self.width() as u32, self.height() as u32)
+    }
+}
+
+impl<const N: usize> DrawTarget for TiledDisplay<N> {
+    type Color = Rgb888;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, rgb) in pixels {
+            self.set_pixel(point.x, point.y, Rgb::from(rgb));
+        }
+        Ok(())
+    }
+
+    fn clear(&mut self, rgb: Self::Color) -> Result<(), Self::Error> {
+        self.fill(Rgb::from(rgb));
+        Ok(())
+    }
+}
+
+impl From<Rgb888> for Rgb {
+    fn from(rgb: Rgb888) -> Self {
+        Rgb::new(rgb.r(), rgb.g(), rgb.b())
+    }
+}
+
+impl From<Rgb> for Rgb888 {
+    fn from(rgb: Rgb) -> Self {
+        Rgb888::new(rgb.r, rgb.g, rgb.b)
+    }
+}
+
+impl From<Rgb888> for Hue {
+    fn from(rgb: Rgb888) -> Self {
+        Hue::from(Rgb::from(rgb))
+    }
+}
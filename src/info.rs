@@ -0,0 +1,93 @@
+//! Identification of the connected device
+//!
+//! [`get_device_info`](crate::My9221LedMatrix::get_device_info) reads the
+//! device ID, the firmware version and the UID of the device at once.
+//!
+//! The version is only reported: the commands are not checked against it,
+//! since no list of the commands supported by each firmware revision is
+//! published, so the driver can't tell which ones a device would ignore.
+//!
+//! # Example
+//!
+//! ```
+//!    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+//!    use grove_matrix_led_my9221_rs::{info::FirmwareVersion, My9221LedMatrix};
+//!
+//!    let mut i2c = I2cMock::new(&[
+//!        I2cTransaction::write_read(0x65, vec![0x00], vec![0x86]),
+//!        I2cTransaction::write_read(0x65, vec![0xe2], vec![0x00, 0x01, 0x00, 0x00]),
+//!        I2cTransaction::write_read(0x65, vec![0xf1], vec![0x2a]),
+//!    ]);
+//!    let led_matrix = My9221LedMatrix::default();
+//!
+//!    let info = led_matrix.get_device_info(&mut i2c)?;
+//!    assert!(info.is_matrix());
+//!    assert_eq!(info.firmware, FirmwareVersion::new(1, 0, 0));
+//!    assert_eq!(info.firmware.to_string(), "1.0.0");
+//!    assert_eq!(info.uid, 0x2a);
+//!
+//!    i2c.done();
+//!    # Ok::<(), grove_matrix_led_my9221_rs::My9221LedMatrixError<embedded_hal::i2c::ErrorKind>>(())
+//! ```
+
+use core::fmt;
+
+use crate::discovery;
+
+/// A firmware version, read as four bytes, the first one being unused
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FirmwareVersion {
+    /// The major version number
+    pub major: u8,
+    /// The minor version number
+    pub minor: u8,
+    /// The patch version number
+    pub patch: u8,
+}
+
+impl FirmwareVersion {
+    /// Create a firmware version
+    pub const fn new(major: u8, minor: u8, patch: u8) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+}
+
+/// As returned by [`test_get_version`](crate::My9221LedMatrix::test_get_version),
+/// the three low bytes being the major, minor and patch numbers
+impl From<u32> for FirmwareVersion {
+    fn from(version: u32) -> Self {
+        let [_, major, minor, patch] = version.to_be_bytes();
+        Self::new(major, minor, patch)
+    }
+}
+
+impl fmt::Display for FirmwareVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// The identification of a device
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeviceInfo {
+    /// The device ID, as returned by
+    /// [`get_device_id`](crate::My9221LedMatrix::get_device_id)
+    pub device_id: u8,
+    /// The firmware version, as returned by
+    /// [`test_get_version`](crate::My9221LedMatrix::test_get_version)
+    pub firmware: FirmwareVersion,
+    /// The UID of the chip, as returned by
+    /// [`get_device_uid`](crate::My9221LedMatrix::get_device_uid)
+    pub uid: u8,
+}
+
+impl DeviceInfo {
+    /// Whether the device ID is the one of the matrices
+    pub fn is_matrix(&self) -> bool {
+        self.device_id == discovery::DEVICE_ID
+    }
+}
@@ -3,44 +3,77 @@
 //!
 //! # Example
 //!
-//! ```rust
-//!    use grove_matrix_led_my9221_rs::GroveMatrixLedMy9221;
-//!
-//!    fn main() {
-//!        let mut led_matrix = grove_matrix_led_my9221_rs::My9221LedMatrix::default();
-//!
-//!        let mut emoji_num = 0;
+//! ```
+//!    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+//!    use grove_matrix_led_my9221_rs::{Emojis, My9221LedMatrix, Playback};
 //!
-//!        const DELAY: u16 = 5_000u16;
+//!    // Any implementation of the embedded-hal `I2c` trait, a mock here
+//!    let mut i2c = I2cMock::new(&[
+//!        I2cTransaction::write_read(0x65, vec![0x00], vec![0x86]),
+//!        I2cTransaction::write(0x65, vec![0x02, 0x00, 0x00, 0x00, 0x01]),
+//!    ]);
+//!    let led_matrix = My9221LedMatrix::default();
 //!
-//!        loop {
-//!            led_matrix.display_emoji(&mut i2c, emoji_num, DELAY, true);
-//!            delay.delay_ms(DELAY);
-//!            emoji_num = (emoji_num + 1) % 32;
-//!        }
-//!    }
+//!    assert_eq!(led_matrix.get_device_id(&mut i2c)?, 0x86);
+//!    led_matrix.display_emoji(&mut i2c, Emojis::Smiley, Playback::Forever)?;
 //!
+//!    i2c.done();
+//!    # Ok::<(), grove_matrix_led_my9221_rs::My9221LedMatrixError<embedded_hal::i2c::ErrorKind>>(())
 //! ```
 #![cfg_attr(not(feature = "std"), no_std)]
-#![feature(destructuring_assignment)]
 
-use embedded_hal::blocking::{
-    delay::DelayMs,
-    i2c::{Read, Write},
+use embedded_hal::{
+    delay::DelayNs,
+    i2c::{self, I2c},
 };
 
+#[macro_use]
+mod execution;
+
+pub mod animation;
+#[cfg(feature = "async")]
+pub mod asynch;
+pub mod color;
+#[cfg(feature = "embedded-hal-02")]
+pub mod compat;
+pub mod compose;
+mod device;
+pub mod discovery;
 mod emojis;
-
+pub mod flash;
+pub mod font;
+mod frame;
+#[cfg(feature = "embedded-graphics")]
+pub mod graphics;
+pub mod info;
+mod playback;
+pub mod player;
+pub mod protocol;
+#[cfg(feature = "render")]
+pub mod render;
+#[cfg(feature = "std")]
+pub mod simulator;
+#[cfg(feature = "std")]
+pub mod terminal;
+pub mod text;
+pub mod tiling;
+
+#[cfg(feature = "async")]
+pub use asynch::My9221LedMatrixAsync;
+pub use color::{Hsv, Hue, Rgb};
+pub use device::My9221LedMatrixDevice;
 pub use emojis::*;
+pub use frame::Frame;
+pub use playback::*;
+
+use animation::Keyframe;
+use flash::FlashSlot;
+use info::{DeviceInfo, FirmwareVersion};
+use protocol::Command;
 
 /// Default I2C Address for the grove matrix LED driver
 const DEFAULT_ADDRESS: u8 = 0x65;
 
-/// A struct representing a frame to display
-pub struct Frame {
-    pub data: [u8; 64],
-}
-
 enum I2cCmd {
     /// This command gets device ID information
     GetDevID = 0x00,
@@ -106,8 +139,47 @@ enum I2cCmd {
     GetDeviceUID = 0xf1,
 }
 
+impl TryFrom<u8> for I2cCmd {
+    type Error = u8;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Ok(match value {
+            0x00 => I2cCmd::GetDevID,
+            0x01 => I2cCmd::DispBar,
+            0x02 => I2cCmd::DispEmoji,
+            0x03 => I2cCmd::DispNum,
+            0x04 => I2cCmd::DispStr,
+            0x05 => I2cCmd::DispCustom,
+            0x06 => I2cCmd::DispOff,
+            0x07 => I2cCmd::DispAscii,
+            0x08 => I2cCmd::DispFlash,
+            0x09 => I2cCmd::DispColorBar,
+            0x0a => I2cCmd::DispColorWave,
+            0x0b => I2cCmd::DispColorClockWise,
+            0x0c => I2cCmd::DispColorAnimation,
+            0x0d => I2cCmd::DispColorBlock,
+            0x81 => I2cCmd::ContinueData,
+            0xa0 => I2cCmd::StoreFlash,
+            0xa1 => I2cCmd::DeleteFlash,
+            0xb0 => I2cCmd::LedOn,
+            0xb1 => I2cCmd::LedOff,
+            0xb2 => I2cCmd::AutoSleepOn,
+            0xb3 => I2cCmd::AutoSleepOff,
+            0xb4 => I2cCmd::DispRotate,
+            0xb5 => I2cCmd::DispOffset,
+            0xc0 => I2cCmd::SetAddress,
+            0xc1 => I2cCmd::ResetAddress,
+            0xe0 => I2cCmd::TestTXRXOn,
+            0xe1 => I2cCmd::TestTXRXOff,
+            0xe2 => I2cCmd::TestGetVersion,
+            0xf1 => I2cCmd::GetDeviceUID,
+            _ => return Err(value),
+        })
+    }
+}
+
 /// An enum representing the possible rotations of the display
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DisplayRotate {
     /// No rotation
     Deg0 = 0,
@@ -120,7 +192,7 @@ pub enum DisplayRotate {
 }
 
 /// An enum representing the animations available in the module
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ColorAnimation {
     BigClockWise = 0,
     SmallClockWise = 1,
@@ -132,7 +204,7 @@ pub enum ColorAnimation {
 
 /// An enum representing the colors available in the module and their
 /// corresponding values
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Colors {
     /// Red
     Red = 0x00,
@@ -157,30 +229,84 @@ pub enum Colors {
 }
 
 /// The grove matrix LED driver
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct My9221LedMatrix {
     address: u8,
 }
 
 /// The specific errors that can occur when communicating with the device
 /// or when using the driver
-#[cfg_attr(feature = "std", derive(Debug))]
-pub enum My9221LedMatrixError {
-    I2CError,
+#[derive(Debug)]
+pub enum My9221LedMatrixError<E> {
+    /// An error reported by the I2C bus
+    I2c(E),
+    /// An argument is not supported by the device
     InvalidArgument,
+    /// A character can't be displayed by the firmware font
+    UnsupportedChar(char),
+    /// An address is reserved by the I2C specification or isn't a 7-bit
+    /// address
+    InvalidAddress(u8),
+    /// Another device already answers at an address
+    AddressInUse(u8),
+    /// The device doesn't answer at the address it was given
+    AddressNotVerified {
+        /// The address the device was given
+        address: u8,
+        /// The address the device answers at after the rollback, which the
+        /// driver uses, `None` if it answers nowhere, or the bus error which
+        /// prevented the rollback
+        rollback: Result<Option<u8>, E>,
+    },
+    /// There aren't enough free slots in flash
+    FlashFull,
+}
+
+impl<E> i2c::Error for My9221LedMatrixError<E>
+where
+    E: i2c::Error,
+{
+    fn kind(&self) -> i2c::ErrorKind {
+        match self {
+            My9221LedMatrixError::I2c(e) => e.kind(),
+            My9221LedMatrixError::InvalidArgument => i2c::ErrorKind::Other,
+            My9221LedMatrixError::UnsupportedChar(_) => i2c::ErrorKind::Other,
+            My9221LedMatrixError::InvalidAddress(_) => i2c::ErrorKind::Other,
+            My9221LedMatrixError::AddressInUse(_) => i2c::ErrorKind::Other,
+            My9221LedMatrixError::AddressNotVerified { .. } => i2c::ErrorKind::Other,
+            My9221LedMatrixError::FlashFull => i2c::ErrorKind::Other,
+        }
+    }
 }
 
 #[cfg(feature = "std")]
-impl std::fmt::Display for My9221LedMatrixError {
+impl<E: std::fmt::Debug> std::fmt::Display for My9221LedMatrixError<E> {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
-            My9221LedMatrixError::I2CError => write!(f, "I2C error"),
+            My9221LedMatrixError::I2c(e) => write!(f, "I2C error: {:?}", e),
             My9221LedMatrixError::InvalidArgument => write!(f, "Invalid argument"),
+            My9221LedMatrixError::UnsupportedChar(c) => write!(f, "Unsupported character {:?}", c),
+            My9221LedMatrixError::InvalidAddress(address) => {
+                write!(f, "Invalid address {:#04x}", address)
+            }
+            My9221LedMatrixError::AddressInUse(address) => {
+                write!(f, "Address {:#04x} already in use", address)
+            }
+            My9221LedMatrixError::AddressNotVerified { address, rollback } => {
+                write!(f, "Device not answering at address {:#04x}, ", address)?;
+                match rollback {
+                    Ok(Some(address)) => write!(f, "answering at {:#04x}", address),
+                    Ok(None) => write!(f, "answering nowhere"),
+                    Err(e) => write!(f, "rollback failed: {:?}", e),
+                }
+            }
+            My9221LedMatrixError::FlashFull => write!(f, "Not enough free flash slots"),
         }
     }
 }
 
 #[cfg(feature = "std")]
-impl std::error::Error for My9221LedMatrixError {}
+impl<E: std::fmt::Debug> std::error::Error for My9221LedMatrixError<E> {}
 
 impl Default for My9221LedMatrix {
     /// Create a new instance of the grove matrix LED driver using the default
@@ -205,6 +331,11 @@ impl My9221LedMatrix {
         Self { address }
     }
 
+    /// The I2C address the driver uses
+    pub fn address(&self) -> u8 {
+        self.address
+    }
+
     /// Get the device ID information
     ///
     /// # Arguments
@@ -213,17 +344,27 @@ impl My9221LedMatrix {
     ///
     /// # Returns
     ///
-    /// * `Result<u8, My9221LedMatrixError>` - Returns the device ID
+    /// * `Result<u8, My9221LedMatrixError<I2C::Error>>` - Returns the device ID
     ///
-    pub fn get_device_id<I2C, E>(&self, i2c: &mut I2C) -> Result<u8, My9221LedMatrixError>
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::i2c::I2c;
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::My9221LedMatrix;
+    ///    # fn example<I2C: I2c>(i2c: &mut I2C) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrix::default();
+    ///    let id = led_matrix.get_device_id(i2c)?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub fn get_device_id<I2C>(&self, i2c: &mut I2C) -> Result<u8, My9221LedMatrixError<I2C::Error>>
     where
-        I2C: Write + Read,
+        I2C: I2c,
     {
         let mut buf = [0u8; 1];
-        i2c.write(self.address, &[I2cCmd::GetDevID as u8])
-            .map_err(|_| My9221LedMatrixError::I2CError)?;
-        i2c.read(self.address, &mut buf)
-            .map_err(|_| My9221LedMatrixError::I2CError)?;
+        self.query(i2c, &Command::GetDeviceId, &mut buf)?;
         Ok(buf[0])
     }
 
@@ -234,20 +375,28 @@ impl My9221LedMatrix {
     /// * `i2c` - The I2C peripheral to use
     /// * `rotate` - The display orientation
     ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::i2c::I2c;
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::{DisplayRotate, My9221LedMatrix};
+    ///    # fn example<I2C: I2c>(i2c: &mut I2C) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrix::default();
+    ///    led_matrix.set_led_matrix_rotate(i2c, DisplayRotate::Deg90)?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
     pub fn set_led_matrix_rotate<I2C>(
         &self,
         i2c: &mut I2C,
         rotate: DisplayRotate,
-    ) -> Result<(), My9221LedMatrixError>
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
     where
-        I2C: Write,
+        I2C: I2c,
     {
-        let mut buf = [0; 2];
-        buf[0] = I2cCmd::DispRotate as u8;
-        buf[1] = rotate as u8;
-        i2c.write(self.address, &buf)
-            .map_err(|_| My9221LedMatrixError::I2CError)?;
-        Ok(())
+        self.write(i2c, &Command::Rotate(rotate))
     }
 
     /// Stop the display
@@ -256,15 +405,24 @@ impl My9221LedMatrix {
     ///
     /// * `i2c` - The I2C peripheral to use
     ///
-    pub fn stop_display<I2C>(&self, i2c: &mut I2C) -> Result<(), My9221LedMatrixError>
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::i2c::I2c;
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::My9221LedMatrix;
+    ///    # fn example<I2C: I2c>(i2c: &mut I2C) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrix::default();
+    ///    led_matrix.stop_display(i2c)?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub fn stop_display<I2C>(&self, i2c: &mut I2C) -> Result<(), My9221LedMatrixError<I2C::Error>>
     where
-        I2C: Write,
+        I2C: I2c,
     {
-        let mut buf = [0; 1];
-        buf[0] = I2cCmd::DispOff as u8;
-        i2c.write(self.address, &buf)
-            .map_err(|_| My9221LedMatrixError::I2CError)?;
-        Ok(())
+        self.write(i2c, &Command::DisplayOff)
     }
 
     /// Set the display offset
@@ -274,21 +432,28 @@ impl My9221LedMatrix {
     /// * `i2c` - The I2C peripheral to use
     /// * `offset` - The display offset (x, y)
     ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::i2c::I2c;
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::My9221LedMatrix;
+    ///    # fn example<I2C: I2c>(i2c: &mut I2C) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrix::default();
+    ///    led_matrix.set_led_matrix_offset(i2c, (1, 2))?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
     pub fn set_led_matrix_offset<I2C>(
         &self,
         i2c: &mut I2C,
         offset: (u8, u8),
-    ) -> Result<(), My9221LedMatrixError>
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
     where
-        I2C: Write,
+        I2C: I2c,
     {
-        let mut buf = [0; 3];
-        buf[0] = I2cCmd::DispOffset as u8;
-        buf[1] = offset.0;
-        buf[2] = offset.1;
-        i2c.write(self.address, &buf)
-            .map_err(|_| My9221LedMatrixError::I2CError)?;
-        Ok(())
+        self.write(i2c, &Command::Offset(offset.0, offset.1))
     }
 
     /// Turn on the display
@@ -297,15 +462,27 @@ impl My9221LedMatrix {
     ///
     /// * `i2c` - The I2C peripheral to use
     ///
-    pub fn turn_on_led_flash<I2C>(&self, i2c: &mut I2C) -> Result<(), My9221LedMatrixError>
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::i2c::I2c;
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::My9221LedMatrix;
+    ///    # fn example<I2C: I2c>(i2c: &mut I2C) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrix::default();
+    ///    led_matrix.turn_on_led_flash(i2c)?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub fn turn_on_led_flash<I2C>(
+        &self,
+        i2c: &mut I2C,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
     where
-        I2C: Write,
+        I2C: I2c,
     {
-        let mut buf = [0; 1];
-        buf[0] = I2cCmd::LedOn as u8;
-        i2c.write(self.address, &buf)
-            .map_err(|_| My9221LedMatrixError::I2CError)?;
-        Ok(())
+        self.write(i2c, &Command::LedFlashOn)
     }
 
     /// Turn off the display
@@ -314,15 +491,27 @@ impl My9221LedMatrix {
     ///
     /// * `i2c` - The I2C peripheral to use
     ///
-    pub fn turn_off_led_flash<I2C>(&self, i2c: &mut I2C) -> Result<(), My9221LedMatrixError>
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::i2c::I2c;
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::My9221LedMatrix;
+    ///    # fn example<I2C: I2c>(i2c: &mut I2C) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrix::default();
+    ///    led_matrix.turn_off_led_flash(i2c)?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub fn turn_off_led_flash<I2C>(
+        &self,
+        i2c: &mut I2C,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
     where
-        I2C: Write,
+        I2C: I2c,
     {
-        let mut buf = [0; 1];
-        buf[0] = I2cCmd::LedOff as u8;
-        i2c.write(self.address, &buf)
-            .map_err(|_| My9221LedMatrixError::I2CError)?;
-        Ok(())
+        self.write(i2c, &Command::LedFlashOff)
     }
 
     /// Enable auto sleep mode
@@ -331,15 +520,27 @@ impl My9221LedMatrix {
     ///
     /// * `i2c` - The I2C peripheral to use
     ///
-    pub fn enable_auto_sleep<I2C>(&self, i2c: &mut I2C) -> Result<(), My9221LedMatrixError>
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::i2c::I2c;
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::My9221LedMatrix;
+    ///    # fn example<I2C: I2c>(i2c: &mut I2C) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrix::default();
+    ///    led_matrix.enable_auto_sleep(i2c)?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub fn enable_auto_sleep<I2C>(
+        &self,
+        i2c: &mut I2C,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
     where
-        I2C: Write,
+        I2C: I2c,
     {
-        let mut buf = [0; 1];
-        buf[0] = I2cCmd::AutoSleepOn as u8;
-        i2c.write(self.address, &buf)
-            .map_err(|_| My9221LedMatrixError::I2CError)?;
-        Ok(())
+        self.write(i2c, &Command::AutoSleepOn)
     }
 
     /// Disable auto sleep mode
@@ -348,15 +549,27 @@ impl My9221LedMatrix {
     ///     
     /// * `i2c` - The I2C peripheral to use
     ///
-    pub fn disable_auto_sleep<I2C>(&self, i2c: &mut I2C) -> Result<(), My9221LedMatrixError>
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::i2c::I2c;
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::My9221LedMatrix;
+    ///    # fn example<I2C: I2c>(i2c: &mut I2C) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrix::default();
+    ///    led_matrix.disable_auto_sleep(i2c)?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub fn disable_auto_sleep<I2C>(
+        &self,
+        i2c: &mut I2C,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
     where
-        I2C: Write,
+        I2C: I2c,
     {
-        let mut buf = [0; 1];
-        buf[0] = I2cCmd::AutoSleepOff as u8;
-        i2c.write(self.address, &buf)
-            .map_err(|_| My9221LedMatrixError::I2CError)?;
-        Ok(())
+        self.write(i2c, &Command::AutoSleepOff)
     }
 
     /// Display a bar
@@ -366,32 +579,40 @@ impl My9221LedMatrix {
     ///
     /// * `i2c` - The I2C peripheral to use
     /// * `bar` - The bar to display
-    /// * `duration_time` - The duration time of the bar
-    /// * `forever_flag` - If true, the bar will be displayed forever
-    /// * `color` - The color of the bar
-    ///
-    pub fn display_bar<I2C>(
+    /// * `playback` - How long to display the bar
+    /// * `color` - The color of the bar, a [`Colors`] or any color convertible
+    ///   to a [`Hue`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::i2c::I2c;
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::{Colors, My9221LedMatrix, Playback};
+    ///    # fn example<I2C: I2c>(i2c: &mut I2C) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrix::default();
+    ///    led_matrix.display_bar(i2c, 16, Playback::Forever, Colors::Green)?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub fn display_bar<I2C, C>(
         &self,
         i2c: &mut I2C,
         bar: u8,
-        duration_time: u16,
-        forever_flag: bool,
-        color: Colors,
-    ) -> Result<(), My9221LedMatrixError>
-    where
-        I2C: Write,
-    {
-        let mut buf = [0; 6];
-        buf[0] = I2cCmd::DispBar as u8;
-        buf[1] = if bar <= 32 { bar } else { 32 };
-        buf[2] = (duration_time & 0xff) as u8;
-        buf[3] = ((duration_time >> 8) & 0xff) as u8;
-        buf[4] = if forever_flag { 1 } else { 0 };
-        buf[5] = color as u8;
-
-        i2c.write(self.address, &buf)
-            .map_err(|_| My9221LedMatrixError::I2CError)?;
-        Ok(())
+        playback: Playback,
+        color: C,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
+    where
+        C: Into<Hue>,
+        I2C: I2c,
+    {
+        let command = Command::DisplayBar {
+            bar: bar.min(32),
+            playback,
+            color: color.into().0,
+        };
+        self.write(i2c, &command)
     }
 
     /// Display an Emoji
@@ -401,29 +622,32 @@ impl My9221LedMatrix {
     ///
     /// * `i2c` - The I2C peripheral to use
     /// * `emoji` - The emoji to display
-    /// * `duration_time` - The duration time of the bar
-    /// * `forever_flag` - If true, the bar will be displayed forever
+    /// * `playback` - How long to display the emoji
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::i2c::I2c;
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::{Emojis, Millis, My9221LedMatrix, Playback};
+    ///    # fn example<I2C: I2c>(i2c: &mut I2C) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrix::default();
+    ///    led_matrix.display_emoji(i2c, Emojis::Heart, Playback::Once(Millis::new(1_000)))?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
     ///
     pub fn display_emoji<I2C>(
         &self,
         i2c: &mut I2C,
         emoji: Emojis,
-        duration_time: u16,
-        forever_flag: bool,
-    ) -> Result<(), My9221LedMatrixError>
+        playback: Playback,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
     where
-        I2C: Write,
+        I2C: I2c,
     {
-        let mut buf = [0; 5];
-        buf[0] = I2cCmd::DispEmoji as u8;
-        buf[1] = emoji as u8;
-        buf[2] = (duration_time & 0xff) as u8;
-        buf[3] = ((duration_time >> 8) & 0xff) as u8;
-        buf[4] = if forever_flag { 1 } else { 0 };
-
-        i2c.write(self.address, &buf)
-            .map_err(|_| My9221LedMatrixError::I2CError)?;
-        Ok(())
+        let command = Command::DisplayEmoji { emoji, playback };
+        self.write(i2c, &command)
     }
 
     /// Display an number
@@ -433,96 +657,149 @@ impl My9221LedMatrix {
     ///
     /// * `i2c` - The I2C peripheral to use
     /// * `number` - The number to display
-    /// * `duration_time` - The duration time of the bar
-    /// * `forever_flag` - If true, the bar will be displayed forever
-    /// * `color` - The color of the number
-    ///
-    pub fn display_number<I2C>(
+    /// * `playback` - How long to display the number
+    /// * `color` - The color of the number, a [`Colors`] or any color convertible
+    ///   to a [`Hue`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::i2c::I2c;
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::{Colors, My9221LedMatrix, Playback};
+    ///    # fn example<I2C: I2c>(i2c: &mut I2C) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrix::default();
+    ///    led_matrix.display_number(i2c, 42, Playback::Forever, Colors::Blue)?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub fn display_number<I2C, C>(
         &self,
         i2c: &mut I2C,
         number: u16,
-        duration_time: u16,
-        forever_flag: bool,
-        color: Colors,
-    ) -> Result<(), My9221LedMatrixError>
-    where
-        I2C: Write,
-    {
-        let mut buf = [0; 7];
-        buf[0] = I2cCmd::DispNum as u8;
-        buf[1] = (number & 0xff) as u8;
-        buf[2] = ((number >> 8) & 0xff) as u8;
-        buf[3] = (duration_time & 0xff) as u8;
-        buf[4] = ((duration_time >> 8) & 0xff & 0xff) as u8;
-        buf[5] = if forever_flag { 1 } else { 0 };
-        buf[6] = color as u8;
-
-        i2c.write(self.address, &buf)
-            .map_err(|_| My9221LedMatrixError::I2CError)?;
-        Ok(())
+        playback: Playback,
+        color: C,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
+    where
+        C: Into<Hue>,
+        I2C: I2c,
+    {
+        let command = Command::DisplayNumber {
+            number,
+            playback,
+            color: color.into().0,
+        };
+        self.write(i2c, &command)
     }
 
     /// Display a string
     ///
+    /// Only printable ASCII characters are supported by the firmware font.
+    /// Strings longer than [`MAX_STRING_LEN`](protocol::MAX_STRING_LEN)
+    /// characters are split in several strings displayed one after the
+    /// other, sharing the playback duration, so this waits until all but the
+    /// last one are displayed. They can't be displayed forever.
+    ///
     /// # Arguments
     ///
     /// * `i2c` - The I2C peripheral to use
     /// * `delay` - A delay provider
     /// * `string` - The string to display
-    /// * `duration_time` - The duration time of the bar
-    /// * `forever_flag` - If true, the bar will be displayed forever
-    /// * `color` - The color of the string
-    ///
-    pub fn display_string<I2C, D, T>(
+    /// * `playback` - How long to display the string
+    /// * `color` - The color of the string, a [`Colors`] or any color convertible
+    ///   to a [`Hue`]
+    ///
+    /// # Errors
+    ///
+    /// * [`My9221LedMatrixError::UnsupportedChar`] - The string has a
+    ///   character which isn't supported, nothing is displayed
+    /// * [`My9221LedMatrixError::InvalidArgument`] - The string is too long to
+    ///   be displayed forever
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::{delay::DelayNs, i2c::I2c};
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::{Colors, My9221LedMatrix, Playback};
+    ///    # fn example<I2C: I2c, D: DelayNs>(
+    ///    #     i2c: &mut I2C,
+    ///    #     delay: &mut D,
+    ///    # ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrix::default();
+    ///    led_matrix.display_string(i2c, delay, "Hi", Playback::Forever, Colors::Red)?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub fn display_string<I2C, D, C>(
         &self,
         i2c: &mut I2C,
         delay: &mut D,
         string: &str,
-        duration_time: u16,
-        forever_flag: bool,
-        color: Colors,
-    ) -> Result<(), My9221LedMatrixError>
+        playback: Playback,
+        color: C,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
     where
-        I2C: Write,
-        D: DelayMs<u32>,
+        C: Into<Hue>,
+        I2C: I2c,
+        D: DelayNs,
     {
-        let mut buf: [u8; 36] = [0; 36];
-        let len = if string.len() >= 28 {
-            28u8
-        } else {
-            string.len() as u8
-        };
-
-        for i in 0..len {
-            buf[(i + 6) as usize] = match string.chars().nth(i as usize) {
-                Some(c) => c as u8,
-                None => 0,
-            };
+        if let Some(c) = string.chars().find(|&c| !protocol::is_supported(c)) {
+            return Err(My9221LedMatrixError::UnsupportedChar(c));
         }
+        self.write_string(i2c, delay, string.chars(), playback, color.into())
+    }
 
-        buf[0] = I2cCmd::DispStr as u8;
-        buf[1] = if forever_flag { 1 } else { 0 };
-        buf[2] = (duration_time & 0xff) as u8;
-        buf[3] = ((duration_time >> 8) & 0xff) as u8;
-        buf[4] = len;
-        buf[5] = color as u8;
-
-        if len > 25 {
-            i2c.write(self.address, &buf[0..31])
-                .map_err(|_| My9221LedMatrixError::I2CError)?;
-            delay.delay_ms(1);
-            let mut buf2: [u8; 6] = [0; 6];
-            buf2[0] = I2cCmd::ContinueData as u8;
-            for i in 31..36 {
-                buf2[(i - 30) as usize] = buf[i as usize];
-            }
-            i2c.write(self.address, &buf2)
-                .map_err(|_| My9221LedMatrixError::I2CError)?;
-        } else {
-            i2c.write(self.address, &buf[0..(len + 6) as usize])
-                .map_err(|_| My9221LedMatrixError::I2CError)?;
-        }
-        Ok(())
+    /// Display a string, replacing the characters which aren't supported by
+    /// the firmware font with the closest supported ones
+    ///
+    /// See [`display_string`](Self::display_string) and
+    /// [`transliterate`](protocol::transliterate).
+    ///
+    /// # Arguments
+    ///
+    /// * `i2c` - The I2C peripheral to use
+    /// * `delay` - A delay provider
+    /// * `string` - The string to display
+    /// * `playback` - How long to display the string
+    /// * `color` - The color of the string, a [`Colors`] or any color convertible
+    ///   to a [`Hue`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::{delay::DelayNs, i2c::I2c};
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::{Colors, My9221LedMatrix, Playback};
+    ///    # fn example<I2C: I2c, D: DelayNs>(
+    ///    #     i2c: &mut I2C,
+    ///    #     delay: &mut D,
+    ///    # ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrix::default();
+    ///    led_matrix.display_string_transliterated(i2c, delay, "Héllo", Playback::Forever, Colors::Red)?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub fn display_string_transliterated<I2C, D, C>(
+        &self,
+        i2c: &mut I2C,
+        delay: &mut D,
+        string: &str,
+        playback: Playback,
+        color: C,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
+    where
+        C: Into<Hue>,
+        I2C: I2c,
+        D: DelayNs,
+    {
+        let chars = string
+            .chars()
+            .flat_map(|c| protocol::transliterate(c).chars());
+        self.write_string(i2c, delay, chars, playback, color.into())
     }
 
     /// Display a color block
@@ -530,33 +807,38 @@ impl My9221LedMatrix {
     /// # Arguments
     ///
     /// * `i2c` - The I2C peripheral to use
-    /// * `rgb` - The color to display in RGB format (0x00RRGGBB)
-    /// * `duration_time` - The duration time of the bar
-    /// * `forever_flag` - If true, the bar will be displayed forever
-    ///
-    pub fn display_color_block<I2C>(
+    /// * `rgb` - The color to display, an [`Rgb`] or a `u32` in RGB format
+    ///   (0x00RRGGBB)
+    /// * `playback` - How long to display the color block
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::i2c::I2c;
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::{color::Rgb, My9221LedMatrix, Playback};
+    ///    # fn example<I2C: I2c>(i2c: &mut I2C) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrix::default();
+    ///    led_matrix.display_color_block(i2c, Rgb::new(0xff, 0x80, 0x00), Playback::Forever)?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub fn display_color_block<I2C, C>(
         &self,
         i2c: &mut I2C,
-        rgb: u32,
-        duration_time: u16,
-        forever_flag: bool,
-    ) -> Result<(), My9221LedMatrixError>
+        rgb: C,
+        playback: Playback,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
     where
-        I2C: Write,
+        C: Into<Rgb>,
+        I2C: I2c,
     {
-        let mut buf: [u8; 7] = [0; 7];
-
-        buf[0] = I2cCmd::DispColorBlock as u8;
-        buf[1] = ((rgb >> 16) & 0xff) as u8;
-        buf[2] = ((rgb >> 8) & 0xff) as u8;
-        buf[3] = (rgb & 0xff) as u8;
-        buf[4] = (duration_time & 0xff) as u8;
-        buf[5] = ((duration_time >> 8) & 0xff) as u8;
-        buf[6] = if forever_flag { 0 } else { 1 };
-
-        i2c.write(self.address, &buf)
-            .map_err(|_| My9221LedMatrixError::I2CError)?;
-        Ok(())
+        let command = Command::DisplayColorBlock {
+            rgb: rgb.into().to_u32(),
+            playback,
+        };
+        self.write(i2c, &command)
     }
 
     /// Display a color bar
@@ -565,30 +847,32 @@ impl My9221LedMatrix {
     ///
     /// * `i2c` - The I2C peripheral to use
     /// * `bar` - the color bar to display
-    /// * `duration_time` - The duration time of the bar
-    /// * `forever_flag` - If true, the bar will be displayed forever
+    /// * `playback` - How long to display the color bar
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::i2c::I2c;
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::{My9221LedMatrix, Playback};
+    ///    # fn example<I2C: I2c>(i2c: &mut I2C) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrix::default();
+    ///    led_matrix.display_color_bar(i2c, 16, Playback::Forever)?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
     ///
     pub fn display_color_bar<I2C>(
         &self,
         i2c: &mut I2C,
         bar: u8,
-        duration_time: u16,
-        forever_flag: bool,
-    ) -> Result<(), My9221LedMatrixError>
+        playback: Playback,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
     where
-        I2C: Write,
+        I2C: I2c,
     {
-        let mut buf: [u8; 5] = [0; 5];
-
-        buf[0] = I2cCmd::DispColorBar as u8;
-        buf[1] = bar;
-        buf[2] = (duration_time & 0xff) as u8;
-        buf[3] = ((duration_time >> 8) & 0xff) as u8;
-        buf[4] = if forever_flag { 0 } else { 1 };
-
-        i2c.write(self.address, &buf)
-            .map_err(|_| My9221LedMatrixError::I2CError)?;
-        Ok(())
+        let command = Command::DisplayColorBar { bar, playback };
+        self.write(i2c, &command)
     }
 
     /// Display a color wave
@@ -597,30 +881,32 @@ impl My9221LedMatrix {
     ///
     /// * `i2c` - The I2C peripheral to use
     /// * `wave` - the color wave to display
-    /// * `duration_time` - The duration time of the bar
-    /// * `forever_flag` - If true, the bar will be displayed forever
+    /// * `playback` - How long to display the wave
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::i2c::I2c;
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::{My9221LedMatrix, Playback};
+    ///    # fn example<I2C: I2c>(i2c: &mut I2C) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrix::default();
+    ///    led_matrix.display_color_wave(i2c, 0, Playback::Forever)?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
     ///
     pub fn display_color_wave<I2C>(
         &self,
         i2c: &mut I2C,
         wave: u8,
-        duration_time: u16,
-        forever_flag: bool,
-    ) -> Result<(), My9221LedMatrixError>
+        playback: Playback,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
     where
-        I2C: Write,
+        I2C: I2c,
     {
-        let mut buf: [u8; 5] = [0; 5];
-
-        buf[0] = I2cCmd::DispColorWave as u8;
-        buf[1] = wave;
-        buf[2] = (duration_time & 0xff) as u8;
-        buf[3] = ((duration_time >> 8) & 0xff) as u8;
-        buf[4] = if forever_flag { 0 } else { 1 };
-
-        i2c.write(self.address, &buf)
-            .map_err(|_| My9221LedMatrixError::I2CError)?;
-        Ok(())
+        let command = Command::DisplayColorWave { wave, playback };
+        self.write(i2c, &command)
     }
 
     /// Display a color clockwise
@@ -630,32 +916,37 @@ impl My9221LedMatrix {
     /// * `i2c` - The I2C peripheral to use
     /// * `clockwise` - If true, the color will be displayed clockwise, if false, anti-clockwise
     /// * `big` - If true, the color clockwise will be displayed in big size, if false, small size
-    /// * `duration_time` - The duration time of the bar
-    /// * `forever_flag` - If true, the bar will be displayed forever
+    /// * `playback` - How long to display the animation
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::i2c::I2c;
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::{My9221LedMatrix, Playback};
+    ///    # fn example<I2C: I2c>(i2c: &mut I2C) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrix::default();
+    ///    led_matrix.display_color_clockwise(i2c, true, false, Playback::Forever)?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
     ///
     pub fn display_color_clockwise<I2C>(
         &self,
         i2c: &mut I2C,
         clockwise: bool,
         big: bool,
-        duration_time: u16,
-        forever_flag: bool,
-    ) -> Result<(), My9221LedMatrixError>
+        playback: Playback,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
     where
-        I2C: Write,
+        I2C: I2c,
     {
-        let mut buf: [u8; 6] = [0; 6];
-
-        buf[0] = I2cCmd::DispColorClockWise as u8;
-        buf[1] = if clockwise { 0 } else { 1 };
-        buf[2] = if big { 0 } else { 1 };
-        buf[3] = (duration_time & 0xff) as u8;
-        buf[4] = ((duration_time >> 8) & 0xff) as u8;
-        buf[5] = if forever_flag { 0 } else { 1 };
-
-        i2c.write(self.address, &buf)
-            .map_err(|_| My9221LedMatrixError::I2CError)?;
-        Ok(())
+        let command = Command::DisplayColorClockwise {
+            clockwise,
+            big,
+            playback,
+        };
+        self.write(i2c, &command)
     }
 
     /// Display a color animation
@@ -670,23 +961,33 @@ impl My9221LedMatrix {
     ///   - `ColorAnimation::Fire`
     ///   - `ColorAnimation::Walking`
     ///   - `ColorAnimation::BrokenHeart`
-    /// * `duration_time` - The duration time of the bar
-    /// * `forever_flag` - If true, the bar will be displayed forever
+    /// * `playback` - How long to display the animation
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::i2c::I2c;
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::{
+    ///        animation::{Animation, Keyframe, Mode}, ColorAnimation, My9221LedMatrix, Playback,
+    ///    };
+    ///    # fn example<I2C: I2c>(i2c: &mut I2C) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrix::default();
+    ///    led_matrix.display_color_animation(i2c, ColorAnimation::RainbowCycle, Playback::Forever)?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
     ///
     pub fn display_color_animation<I2C>(
         &self,
         i2c: &mut I2C,
         animation_index: ColorAnimation,
-        duration_time: u16,
-        forever_flag: bool,
-    ) -> Result<(), My9221LedMatrixError>
+        playback: Playback,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
     where
-        I2C: Write,
+        I2C: I2c,
     {
-        let mut buf: [u8; 6] = [0; 6];
-
-        buf[0] = I2cCmd::DispColorAnimation as u8;
-        (buf[1], buf[2]) = match animation_index {
+        let (from_idx, to_idx) = match animation_index {
             ColorAnimation::BigClockWise => (0, 28),    // big clockwise
             ColorAnimation::SmallClockWise => (29, 41), // small clockwise
             ColorAnimation::RainbowCycle => (255, 255), // rainbow cycle
@@ -694,13 +995,12 @@ impl My9221LedMatrix {
             ColorAnimation::Walking => (42, 43),        // walking
             ColorAnimation::BrokenHeart => (44, 52),    // broken heart
         };
-        buf[3] = (duration_time & 0xff) as u8;
-        buf[4] = ((duration_time >> 8) & 0xff) as u8;
-        buf[5] = if forever_flag { 0 } else { 1 };
-
-        i2c.write(self.address, &buf)
-            .map_err(|_| My9221LedMatrixError::I2CError)?;
-        Ok(())
+        let command = Command::DisplayColorAnimation {
+            from_idx,
+            to_idx,
+            playback,
+        };
+        self.write(i2c, &command)
     }
 
     /// Display the frame
@@ -710,62 +1010,139 @@ impl My9221LedMatrix {
     /// * `i2c` - The I2C peripheral to use
     /// * `delay` - A delay provider
     /// * `frames` - The frames to display
-    /// * `duration_time` - The duration time of the bar
-    /// * `forever_flag` - If true, the bar will be displayed forever
+    /// * `playback` - How long to display the frames
     /// * `frame_number` - The total number of frames
     ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::{delay::DelayNs, i2c::I2c};
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::{Colors, Frame, My9221LedMatrix, Playback};
+    ///    # fn example<I2C: I2c, D: DelayNs>(
+    ///    #     i2c: &mut I2C,
+    ///    #     delay: &mut D,
+    ///    # ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrix::default();
+    ///    let frames = [Frame::filled(Colors::Red), Frame::filled(Colors::Blue)];
+    ///    led_matrix.display_frames(i2c, delay, &frames, Playback::Forever, 2)?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
     pub fn display_frames<I2C, D>(
         &self,
         i2c: &mut I2C,
         delay: &mut D,
         frames: &[Frame],
-        duration_time: u16,
-        forever_flag: bool,
+        playback: Playback,
         frames_number: u8,
-    ) -> Result<(), My9221LedMatrixError>
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
     where
-        I2C: Write,
-        D: DelayMs<u32>,
+        I2C: I2c,
+        D: DelayNs,
     {
-        let mut buf: [u8; 72] = [0; 72];
+        self.write_frames(i2c, delay, frames, playback, frames_number)
+    }
 
-        let frames_number = if frames_number > 5 {
-            5
-        } else if frames_number == 0 {
-            return Err(My9221LedMatrixError::InvalidArgument);
-        } else {
-            frames_number
-        };
+    /// Display frames one after the other, each for `frame_time`
+    ///
+    /// Unlike [`display_frames`](Self::display_frames), the number of frames
+    /// is not limited by the device, since each of them is sent when it is
+    /// displayed, for example the frames of a [`TextFrames`](crate::text::TextFrames).
+    ///
+    /// # Arguments
+    ///
+    /// * `i2c` - The I2C peripheral to use
+    /// * `delay` - A delay provider
+    /// * `frames` - The frames to display
+    /// * `frame_time` - How long to display each frame
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::{delay::DelayNs, i2c::I2c};
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::{Colors, Frame, Millis, My9221LedMatrix};
+    ///    # fn example<I2C: I2c, D: DelayNs>(
+    ///    #     i2c: &mut I2C,
+    ///    #     delay: &mut D,
+    ///    # ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrix::default();
+    ///    let frames = [Frame::filled(Colors::Red), Frame::filled(Colors::Blue)];
+    ///    led_matrix.stream_frames(i2c, delay, frames, Millis::new(100))?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub fn stream_frames<I2C, D, I>(
+        &self,
+        i2c: &mut I2C,
+        delay: &mut D,
+        frames: I,
+        frame_time: Millis,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
+    where
+        I2C: I2c,
+        D: DelayNs,
+        I: IntoIterator<Item = Frame>,
+    {
+        let frames = frames.into_iter().map(|frame| (frame, frame_time));
+        self.write_timed_frames(i2c, delay, frames)
+    }
 
-        buf[0] = I2cCmd::DispCustom as u8;
-        buf[1] = 0;
-        buf[2] = 0;
-        buf[3] = 0;
-        buf[4] = frames_number;
-
-        for i in (0..frames_number).rev() {
-            buf[5] = i;
-            buf[8..(64 + 8)].copy_from_slice(&frames[i as usize].data[..64]);
-            if i == 0 {
-                buf[1] = (duration_time & 0xff) as u8;
-                buf[2] = ((duration_time >> 8) & 0xff) as u8;
-                buf[3] = if forever_flag { 0 } else { 1 };
-            }
-            i2c.write(self.address, &buf[0..24])
-                .map_err(|_| My9221LedMatrixError::I2CError)?;
-            delay.delay_ms(10);
-            let mut buf2: [u8; 25] = [0; 25];
-            buf2[0] = I2cCmd::ContinueData as u8;
-            buf2[1..(24 + 1)].copy_from_slice(&buf[24..(24 + 24)]);
-            i2c.write(self.address, &buf2)
-                .map_err(|_| My9221LedMatrixError::I2CError)?;
-            let mut buf2: [u8; 25] = [0; 25];
-            buf2[0] = I2cCmd::ContinueData as u8;
-            buf2[1..(24 + 1)].copy_from_slice(&buf[48..(24 + 48)]);
-            i2c.write(self.address, &buf2)
-                .map_err(|_| My9221LedMatrixError::I2CError)?;
-        }
-        Ok(())
+    /// Play the keyframes of an animation, each one for its own duration
+    ///
+    /// This blocks until the animation ends, which never happens for
+    /// [`Mode::Loop`](animation::Mode::Loop) and
+    /// [`Mode::PingPong`](animation::Mode::PingPong) animations, unless their
+    /// steps are limited, for example with
+    /// `animation.steps().take(n)`.
+    ///
+    /// # Arguments
+    ///
+    /// * `i2c` - The I2C peripheral to use
+    /// * `delay` - A delay provider
+    /// * `steps` - The keyframes to display, an [`Animation`](animation::Animation)
+    ///   or its [`Steps`](animation::Steps)
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::{delay::DelayNs, i2c::I2c};
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::{
+    ///        animation::{Animation, Keyframe, Mode}, Colors, Frame, Millis, My9221LedMatrix,
+    ///    };
+    ///    # fn example<I2C: I2c, D: DelayNs>(
+    ///    #     i2c: &mut I2C,
+    ///    #     delay: &mut D,
+    ///    # ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrix::default();
+    ///    let keyframes = [
+    ///        Keyframe::new(Frame::filled(Colors::Red), Millis::new(100)),
+    ///        Keyframe::new(Frame::filled(Colors::Blue), Millis::new(500)),
+    ///    ];
+    ///    led_matrix.play_animation(i2c, delay, &Animation::new(&keyframes, Mode::Once))?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub fn play_animation<'a, I2C, D, I>(
+        &self,
+        i2c: &mut I2C,
+        delay: &mut D,
+        steps: I,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
+    where
+        I2C: I2c,
+        D: DelayNs,
+        I: IntoIterator<Item = &'a Keyframe>,
+    {
+        let frames = steps
+            .into_iter()
+            .map(|keyframe| (keyframe.frame, keyframe.duration));
+        self.write_timed_frames(i2c, delay, frames)
     }
 
     /// Store frames to the internal buffer
@@ -775,19 +1152,32 @@ impl My9221LedMatrix {
     /// * `i2c` - The I2C peripheral to use
     /// * `delay` - A delay provider
     ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::{delay::DelayNs, i2c::I2c};
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::My9221LedMatrix;
+    ///    # fn example<I2C: I2c, D: DelayNs>(
+    ///    #     i2c: &mut I2C,
+    ///    #     delay: &mut D,
+    ///    # ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrix::default();
+    ///    led_matrix.store_frames(i2c, delay)?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
     pub fn store_frames<I2C, D>(
         &self,
         i2c: &mut I2C,
         delay: &mut D,
-    ) -> Result<(), My9221LedMatrixError>
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
     where
-        I2C: Write,
-        D: DelayMs<u32>,
+        I2C: I2c,
+        D: DelayNs,
     {
-        i2c.write(self.address, &[I2cCmd::StoreFlash as u8])
-            .map_err(|_| My9221LedMatrixError::I2CError)?;
-        delay.delay_ms(200);
-        Ok(())
+        self.write_with_delay(i2c, delay, &Command::StoreFlash)
     }
 
     /// Delete frames from the internal buffer
@@ -797,19 +1187,32 @@ impl My9221LedMatrix {
     /// * `i2c` - The I2C peripheral to use
     /// * `delay` - A delay provider
     ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::{delay::DelayNs, i2c::I2c};
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::My9221LedMatrix;
+    ///    # fn example<I2C: I2c, D: DelayNs>(
+    ///    #     i2c: &mut I2C,
+    ///    #     delay: &mut D,
+    ///    # ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrix::default();
+    ///    led_matrix.delete_frames(i2c, delay)?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
     pub fn delete_frames<I2C, D>(
         &self,
         i2c: &mut I2C,
         delay: &mut D,
-    ) -> Result<(), My9221LedMatrixError>
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
     where
-        I2C: Write,
-        D: DelayMs<u32>,
+        I2C: I2c,
+        D: DelayNs,
     {
-        i2c.write(self.address, &[I2cCmd::DeleteFlash as u8])
-            .map_err(|_| My9221LedMatrixError::I2CError)?;
-        delay.delay_ms(200);
-        Ok(())
+        self.write_with_delay(i2c, delay, &Command::DeleteFlash)
     }
 
     /// Display frames from the internal buffer
@@ -817,51 +1220,43 @@ impl My9221LedMatrix {
     /// # Arguments
     ///
     /// * `i2c` - The I2C peripheral to use
-    /// * `duration_time` - The duration time of the bar
-    /// * `forever_flag` - If true, the bar will be displayed forever
-    /// * `from_idx` - The index of the first frame to display
-    /// * `to_idx` - The index of the last frame to display
+    /// * `playback` - How long to display the frames
+    /// * `from_idx` - The slot of the first frame to display
+    /// * `to_idx` - The slot of the last frame to display, which can't be
+    ///   before `from_idx`
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::i2c::I2c;
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::{flash::FlashSlot, My9221LedMatrix, Playback};
+    ///    # fn example<I2C: I2c>(i2c: &mut I2C) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrix::default();
+    ///    led_matrix.display_frames_from_flash(i2c, Playback::Forever, FlashSlot::Slot1, FlashSlot::Slot2)?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
     ///
     pub fn display_frames_from_flash<I2C>(
         &self,
         i2c: &mut I2C,
-        duration_time: u16,
-        forever_flag: bool,
-        from_idx: u8,
-        to_idx: u8,
-    ) -> Result<(), My9221LedMatrixError>
-    where
-        I2C: Write,
-    {
-        let from_idx = if from_idx > 5 {
-            5
-        } else if from_idx < 1 {
-            1
-        } else {
-            from_idx
-        };
-        let to_idx = if to_idx > 5 {
-            5
-        } else if to_idx < 1 {
-            1
-        } else {
-            to_idx
-        };
-        let (from_idx, to_idx) = if from_idx > to_idx {
-            (to_idx, from_idx)
-        } else {
-            (from_idx, to_idx)
+        playback: Playback,
+        from_idx: FlashSlot,
+        to_idx: FlashSlot,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
+    where
+        I2C: I2c,
+    {
+        if from_idx > to_idx {
+            return Err(My9221LedMatrixError::InvalidArgument);
+        }
+        let command = Command::DisplayFlash {
+            playback,
+            from_idx: from_idx.index(),
+            to_idx: to_idx.index(),
         };
-        let mut buf: [u8; 6] = [0; 6];
-        buf[0] = I2cCmd::DispFlash as u8;
-        buf[1] = (duration_time & 0xff) as u8;
-        buf[2] = ((duration_time >> 8) & 0xff) as u8;
-        buf[3] = if forever_flag { 0 } else { 1 };
-        buf[4] = from_idx;
-        buf[5] = to_idx;
-        i2c.write(self.address, &buf)
-            .map_err(|_| My9221LedMatrixError::I2CError)?;
-        Ok(())
+        self.write(i2c, &command)
     }
 
     /// Enable test mode
@@ -870,17 +1265,27 @@ impl My9221LedMatrix {
     ///
     /// * `i2c` - The I2C peripheral to use
     ///
-    pub fn enable_test_mode<I2C>(&self, i2c: &mut I2C) -> Result<(), My9221LedMatrixError>
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::i2c::I2c;
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::My9221LedMatrix;
+    ///    # fn example<I2C: I2c>(i2c: &mut I2C) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrix::default();
+    ///    led_matrix.enable_test_mode(i2c)?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub fn enable_test_mode<I2C>(
+        &self,
+        i2c: &mut I2C,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
     where
-        I2C: Write,
+        I2C: I2c,
     {
-        let mut buf: [u8; 1] = [0; 1];
-
-        buf[0] = I2cCmd::TestTXRXOn as u8;
-
-        i2c.write(self.address, &buf)
-            .map_err(|_| My9221LedMatrixError::I2CError)?;
-        Ok(())
+        self.write(i2c, &Command::TestModeOn)
     }
 
     /// Disable test mode
@@ -889,17 +1294,27 @@ impl My9221LedMatrix {
     ///
     /// * `i2c` - The I2C peripheral to use
     ///
-    pub fn disable_test_mode<I2C>(&self, i2c: &mut I2C) -> Result<(), My9221LedMatrixError>
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::i2c::I2c;
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::My9221LedMatrix;
+    ///    # fn example<I2C: I2c>(i2c: &mut I2C) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrix::default();
+    ///    led_matrix.disable_test_mode(i2c)?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub fn disable_test_mode<I2C>(
+        &self,
+        i2c: &mut I2C,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
     where
-        I2C: Write,
+        I2C: I2c,
     {
-        let mut buf: [u8; 1] = [0; 1];
-
-        buf[0] = I2cCmd::TestTXRXOff as u8;
-
-        i2c.write(self.address, &buf)
-            .map_err(|_| My9221LedMatrixError::I2CError)?;
-        Ok(())
+        self.write(i2c, &Command::TestModeOff)
     }
 
     /// Test getting the version
@@ -910,23 +1325,31 @@ impl My9221LedMatrix {
     ///
     /// # Returns
     ///
-    /// * `Result<u8, My9221LedMatrixError>` - Returns the device version
+    /// * `Result<u32, My9221LedMatrixError<I2C::Error>>` - Returns the device version
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::i2c::I2c;
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::My9221LedMatrix;
+    ///    # fn example<I2C: I2c>(i2c: &mut I2C) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrix::default();
+    ///    let version = led_matrix.test_get_version(i2c)?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
     ///
-    pub fn test_get_version<I2C>(&self, i2c: &mut I2C) -> Result<u32, My9221LedMatrixError>
+    pub fn test_get_version<I2C>(
+        &self,
+        i2c: &mut I2C,
+    ) -> Result<u32, My9221LedMatrixError<I2C::Error>>
     where
-        I2C: Write + Read,
+        I2C: I2c,
     {
-        let mut cmd: [u8; 1] = [0; 1];
         let mut buf: [u8; 4] = [0; 4];
-
-        cmd[0] = I2cCmd::TestGetVersion as u8;
-
-        i2c.write(self.address, &cmd)
-            .map_err(|_| My9221LedMatrixError::I2CError)?;
-        i2c.read(self.address, &mut buf)
-            .map_err(|_| My9221LedMatrixError::I2CError)?;
-
-        Ok((buf[0] as u32) << 24 | (buf[1] as u32) << 16 | (buf[2] as u32) << 8 | (buf[3] as u32))
+        self.query(i2c, &Command::GetVersion, &mut buf)?;
+        Ok(u32::from_be_bytes(buf))
     }
 
     /// Get the device UID
@@ -937,47 +1360,104 @@ impl My9221LedMatrix {
     ///
     /// # Returns
     ///
-    /// * `Result<u8, My9221LedMatrixError>` - Returns the device UID
+    /// * `Result<u8, My9221LedMatrixError<I2C::Error>>` - Returns the device UID
+    ///
+    /// # Example
     ///
-    pub fn get_device_uid<I2C>(&self, i2c: &mut I2C) -> Result<u8, My9221LedMatrixError>
+    /// ```
+    ///    # use embedded_hal::i2c::I2c;
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::My9221LedMatrix;
+    ///    # fn example<I2C: I2c>(i2c: &mut I2C) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrix::default();
+    ///    let uid = led_matrix.get_device_uid(i2c)?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub fn get_device_uid<I2C>(&self, i2c: &mut I2C) -> Result<u8, My9221LedMatrixError<I2C::Error>>
     where
-        I2C: Write + Read,
+        I2C: I2c,
     {
-        let mut cmd: [u8; 1] = [0; 1];
         let mut buf: [u8; 1] = [0; 1];
-
-        cmd[0] = I2cCmd::GetDeviceUID as u8;
-
-        i2c.write(self.address, &cmd)
-            .map_err(|_| My9221LedMatrixError::I2CError)?;
-        i2c.read(self.address, &mut buf)
-            .map_err(|_| My9221LedMatrixError::I2CError)?;
-
+        self.query(i2c, &Command::GetDeviceUid, &mut buf)?;
         Ok(buf[0])
     }
 
+    /// Get the device ID, the firmware version and the UID of the device
+    ///
+    /// # Arguments
+    ///
+    /// * `i2c` - The I2C peripheral to use
+    ///
+    /// # Returns
+    ///
+    /// * `Result<DeviceInfo, My9221LedMatrixError<I2C::Error>>` - Returns the
+    ///   device information
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::i2c::I2c;
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::My9221LedMatrix;
+    ///    # fn example<I2C: I2c>(i2c: &mut I2C) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let led_matrix = My9221LedMatrix::default();
+    ///    let info = led_matrix.get_device_info(i2c)?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub fn get_device_info<I2C>(
+        &self,
+        i2c: &mut I2C,
+    ) -> Result<DeviceInfo, My9221LedMatrixError<I2C::Error>>
+    where
+        I2C: I2c,
+    {
+        Ok(DeviceInfo {
+            device_id: self.get_device_id(i2c)?,
+            firmware: FirmwareVersion::from(self.test_get_version(i2c)?),
+            uid: self.get_device_uid(i2c)?,
+        })
+    }
+
     /// Set the address of the device
     ///
+    /// The device isn't checked to answer at its new address, see
+    /// [`readdress`](Self::readdress).
+    ///
     /// # Arguments
     ///
     /// * `i2c` - The I2C peripheral to use
-    /// * `address` - The new address of the device
+    /// * `address` - The new address of the device, a 7-bit address which
+    ///   isn't reserved by the I2C specification
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::i2c::I2c;
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::My9221LedMatrix;
+    ///    # fn example<I2C: I2c>(i2c: &mut I2C) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let mut led_matrix = My9221LedMatrix::default();
+    ///    led_matrix.set_address(i2c, 0x66)?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
     ///
     pub fn set_address<I2C>(
         &mut self,
         i2c: &mut I2C,
         address: u8,
-    ) -> Result<(), My9221LedMatrixError>
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
     where
-        I2C: Write,
+        I2C: I2c,
     {
-        let mut buf: [u8; 2] = [0; 2];
-
-        buf[0] = I2cCmd::SetAddress as u8;
-        buf[1] = address;
-
-        i2c.write(self.address, &buf)
-            .map_err(|_| My9221LedMatrixError::I2CError)?;
+        if !discovery::is_valid_address(address) {
+            return Err(My9221LedMatrixError::InvalidAddress(address));
+        }
+        self.write(i2c, &Command::SetAddress(address))?;
         self.address = address;
         Ok(())
     }
@@ -988,17 +1468,104 @@ impl My9221LedMatrix {
     ///
     /// * `i2c` - The I2C peripheral to use
     ///
-    pub fn reset_address<I2C>(&mut self, i2c: &mut I2C) -> Result<(), My9221LedMatrixError>
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::i2c::I2c;
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::My9221LedMatrix;
+    ///    # fn example<I2C: I2c>(i2c: &mut I2C) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let mut led_matrix = My9221LedMatrix::default();
+    ///    led_matrix.reset_address(i2c)?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub fn reset_address<I2C>(
+        &mut self,
+        i2c: &mut I2C,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
     where
-        I2C: Write,
+        I2C: I2c,
     {
-        let mut buf: [u8; 1] = [0; 1];
-
-        buf[0] = I2cCmd::ResetAddress as u8;
-
-        i2c.write(self.address, &buf)
-            .map_err(|_| My9221LedMatrixError::I2CError)?;
+        self.write(i2c, &Command::ResetAddress)?;
         self.address = DEFAULT_ADDRESS;
         Ok(())
     }
+
+    /// Move the device to another address, checking nothing else answers
+    /// there beforehand and that the device answers there afterwards
+    ///
+    /// If the device doesn't answer at its new address, and doesn't answer
+    /// at its previous one either, its address is reset and the driver uses
+    /// the default address if the device answers there. The outcome of this
+    /// rollback is returned in
+    /// [`AddressNotVerified`](crate::My9221LedMatrixError::AddressNotVerified),
+    /// and the address used afterwards is given by
+    /// [`address`](Self::address).
+    ///
+    /// # Arguments
+    ///
+    /// * `i2c` - The I2C peripheral to use
+    /// * `delay` - A delay provider
+    /// * `address` - The new address of the device, a 7-bit address which
+    ///   isn't reserved by the I2C specification
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///    # use embedded_hal::{delay::DelayNs, i2c::I2c};
+    ///    # use grove_matrix_led_my9221_rs::My9221LedMatrixError;
+    ///    use grove_matrix_led_my9221_rs::My9221LedMatrix;
+    ///    # fn example<I2C: I2c, D: DelayNs>(
+    ///    #     i2c: &mut I2C,
+    ///    #     delay: &mut D,
+    ///    # ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+    ///    let mut led_matrix = My9221LedMatrix::default();
+    ///    led_matrix.readdress(i2c, delay, 0x66)?;
+    ///    # Ok(())
+    ///    # }
+    /// ```
+    ///
+    pub fn readdress<I2C, D>(
+        &mut self,
+        i2c: &mut I2C,
+        delay: &mut D,
+        address: u8,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
+    where
+        I2C: I2c,
+        D: DelayNs,
+    {
+        self.move_to(i2c, delay, address)
+    }
 }
+
+/// Validation of the commands
+impl My9221LedMatrix {
+    /// Refuse the commands the device would ignore or misinterpret, before
+    /// anything is written
+    pub(crate) fn check<E>(command: &Command) -> Result<(), My9221LedMatrixError<E>> {
+        let valid = match *command {
+            Command::DisplayCustom {
+                index,
+                frames_number,
+                ..
+            } => (1..=5).contains(&frames_number) && index < frames_number,
+            Command::DisplayFlash {
+                from_idx, to_idx, ..
+            } => {
+                matches!((FlashSlot::try_from(from_idx), FlashSlot::try_from(to_idx)),
+                    (Ok(from_idx), Ok(to_idx)) if from_idx <= to_idx)
+            }
+            _ => true,
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(My9221LedMatrixError::InvalidArgument)
+        }
+    }
+}
+
+execution!(My9221LedMatrix, [], []);
@@ -0,0 +1,126 @@
+//! How long the device displays something
+
+use core::fmt;
+
+/// A duration in milliseconds, in the range accepted by the device
+/// (0 to 65535 ms)
+///
+/// # Example
+///
+/// ```
+///    use core::time::Duration;
+///    use grove_matrix_led_my9221_rs::Millis;
+///
+///    assert_eq!(Millis::try_from(Duration::from_secs(1)), Ok(Millis::new(1_000)));
+///    assert!(Millis::try_from(70_000u32).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Millis(u16);
+
+impl Millis {
+    /// The longest duration accepted by the device
+    pub const MAX: Millis = Millis(u16::MAX);
+
+    /// Create a duration from a number of milliseconds
+    pub const fn new(ms: u16) -> Self {
+        Self(ms)
+    }
+
+    /// The number of milliseconds
+    pub const fn as_ms(&self) -> u16 {
+        self.0
+    }
+}
+
+/// The error returned when a duration is too long for the device
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutOfRangeError;
+
+impl fmt::Display for OutOfRangeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Duration out of range (max {} ms)", u16::MAX)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for OutOfRangeError {}
+
+impl From<u16> for Millis {
+    fn from(ms: u16) -> Self {
+        Self(ms)
+    }
+}
+
+impl TryFrom<u32> for Millis {
+    type Error = OutOfRangeError;
+
+    fn try_from(ms: u32) -> Result<Self, Self::Error> {
+        u16::try_from(ms).map(Self).map_err(|_| OutOfRangeError)
+    }
+}
+
+impl TryFrom<core::time::Duration> for Millis {
+    type Error = OutOfRangeError;
+
+    /// Convert a duration, rounded down to the millisecond
+    fn try_from(duration: core::time::Duration) -> Result<Self, Self::Error> {
+        u16::try_from(duration.as_millis())
+            .map(Self)
+            .map_err(|_| OutOfRangeError)
+    }
+}
+
+impl From<Millis> for core::time::Duration {
+    fn from(ms: Millis) -> Self {
+        core::time::Duration::from_millis(ms.0 as u64)
+    }
+}
+
+/// How long the device displays something
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Playback {
+    /// Display for the given duration, then turn the display off
+    Once(Millis),
+    /// Display until something else is displayed
+    Forever,
+}
+
+impl Playback {
+    /// The duration time and forever flag sent to the device
+    ///
+    /// The flag is `inverted` for the commands sending 0 to display forever
+    /// and 1 to display once, as the custom frames, the frames stored in
+    /// flash and the built-in color animations do.
+    pub(crate) fn encode(&self, inverted: bool) -> ([u8; 2], u8) {
+        let (duration_time, forever) = match self {
+            Playback::Once(ms) => (ms.0.to_le_bytes(), false),
+            Playback::Forever => ([0, 0], true),
+        };
+        (duration_time, (forever != inverted) as u8)
+    }
+
+    /// Decode the duration time and forever flag sent to the device, the
+    /// flag being `inverted` as for [`encode`](Self::encode)
+    pub(crate) fn decode(duration_time: [u8; 2], forever_flag: u8, inverted: bool) -> Self {
+        if (forever_flag != 0) != inverted {
+            Playback::Forever
+        } else {
+            Playback::Once(Millis(u16::from_le_bytes(duration_time)))
+        }
+    }
+}
+
+impl From<Millis> for Playback {
+    fn from(ms: Millis) -> Self {
+        Playback::Once(ms)
+    }
+}
+
+impl fmt::Display for Playback {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Playback::Once(ms) => write!(f, "for {} ms", ms.0),
+            Playback::Forever => write!(f, "forever"),
+        }
+    }
+}
@@ -0,0 +1,390 @@
+//! Non-blocking player
+//!
+//! The methods of [`My9221LedMatrix`] wait while the
+//! device processes multi-packet commands and while animations are
+//! displayed. A [`Player`] instead only starts a job, which is carried out by
+//! calling [`poll`](Player::poll) from the main loop or a timer task: each
+//! call writes at most one packet and tells when the player needs to be
+//! polled again.
+//!
+//! Time is given as a millisecond counter which may wrap around.
+//!
+//! # Example
+//!
+//! ```
+//!    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+//!    use grove_matrix_led_my9221_rs::{
+//!        animation::{Animation, Keyframe, Mode},
+//!        player::Player,
+//!        protocol::Command,
+//!        Colors, Frame, Millis, Playback,
+//!    };
+//!
+//!    let keyframes = [
+//!        Keyframe::new(Frame::filled(Colors::Red), Millis::new(100)),
+//!        Keyframe::new(Frame::filled(Colors::Blue), Millis::new(100)),
+//!    ];
+//!    let expectations: Vec<_> = keyframes
+//!        .iter()
+//!        .flat_map(|keyframe| {
+//!            let command = Command::DisplayCustom {
+//!                frame: keyframe.frame,
+//!                index: 0,
+//!                frames_number: 1,
+//!                playback: Playback::Forever,
+//!            };
+//!            command.packets()
+//!        })
+//!        .map(|packet| I2cTransaction::write(0x65, packet.as_bytes().to_vec()))
+//!        .collect();
+//!
+//!    let mut player = Player::new(I2cMock::new(&expectations), 0x65);
+//!    player.play_animation(&Animation::new(&keyframes, Mode::Once))?;
+//!
+//!    // A frame is written in 3 packets, the device needs 10 ms after the
+//!    // first one
+//!    assert_eq!(player.poll(0)?, Some(10));
+//!    assert_eq!(player.poll(5)?, Some(10));
+//!    assert_eq!(player.poll(10)?, Some(10));
+//!    assert_eq!(player.poll(10)?, Some(110));
+//!    // Then the second frame
+//!    assert_eq!(player.poll(110)?, Some(120));
+//!    assert_eq!(player.poll(120)?, Some(120));
+//!    assert_eq!(player.poll(120)?, None);
+//!
+//!    player.release().done();
+//!    # Ok::<(), grove_matrix_led_my9221_rs::My9221LedMatrixError<embedded_hal::i2c::ErrorKind>>(())
+//! ```
+
+use core::iter::{FlatMap, Peekable};
+use core::str::Chars;
+
+use embedded_hal::i2c::I2c;
+
+use crate::{
+    animation::{Animation, Steps},
+    protocol::{self, Command, Packets, Texts},
+    Frame, Hue, My9221LedMatrix, My9221LedMatrixError, Playback,
+};
+
+/// Characters of a string, replaced by the closest ones supported by the
+/// firmware font
+type Transliterated<'a> = FlatMap<Chars<'a>, Chars<'static>, fn(char) -> Chars<'static>>;
+
+/// The commands left to write for the current job
+enum Job<'a> {
+    Idle,
+    Command(Command),
+    Frames {
+        frames: &'a [Frame],
+        remaining: u8,
+        frames_number: u8,
+        playback: Playback,
+    },
+    String {
+        texts: Peekable<Texts<Transliterated<'a>>>,
+        color: Hue,
+    },
+    Animation(Steps<'a>),
+}
+
+impl Job<'_> {
+    /// The next command and how long to wait once it is written, in ms
+    fn next(&mut self) -> Option<(Command, u32)> {
+        match self {
+            Job::Idle => None,
+            Job::Command(command) => {
+                let command = *command;
+                *self = Job::Idle;
+                Some((command, 0))
+            }
+            Job::Frames {
+                frames,
+                remaining,
+                frames_number,
+                playback,
+            } => {
+                // Frames are sent from the last one to the first one
+                *remaining = remaining.checked_sub(1)?;
+                let command = Command::DisplayCustom {
+                    frame: frames[*remaining as usize],
+                    index: *remaining,
+                    frames_number: *frames_number,
+                    playback: *playback,
+                };
+                Some((command, 0))
+            }
+            Job::String { texts, color } => {
+                let (text, playback) = texts.next()?;
+                let wait = match (texts.peek(), playback) {
+                    (Some(_), Playback::Once(duration)) => duration.as_ms() as u32,
+                    _ => 0,
+                };
+                let command = Command::DisplayString {
+                    text,
+                    playback,
+                    color: color.0,
+                };
+                Some((command, wait))
+            }
+            Job::Animation(steps) => {
+                let keyframe = steps.next()?;
+                let command = Command::DisplayCustom {
+                    frame: keyframe.frame,
+                    index: 0,
+                    frames_number: 1,
+                    playback: Playback::Forever,
+                };
+                Some((command, keyframe.duration.as_ms() as u32))
+            }
+        }
+    }
+}
+
+/// A driver carrying out its jobs one packet at a time, owning its I2C bus
+pub struct Player<'a, I2C> {
+    i2c: I2C,
+    address: u8,
+    job: Job<'a>,
+    /// The first command of the job and how long to wait once it is written,
+    /// taken when the job is started to check it
+    first: Option<(Command, u32)>,
+    /// The packets left to write for the current command
+    packets: Option<Peekable<Packets>>,
+    /// How long to wait once the current command is written, in ms
+    wait: u32,
+    /// When the device is ready for the next packet, if it is busy
+    next: Option<u32>,
+}
+
+impl<'a, I2C> Player<'a, I2C>
+where
+    I2C: I2c,
+{
+    /// Create an idle player
+    ///
+    /// # Arguments
+    ///
+    /// * `i2c` - The I2C peripheral to use
+    /// * `address` - The I2C address to use (default is 0x65)
+    ///
+    pub fn new(i2c: I2C, address: u8) -> Self {
+        Self {
+            i2c,
+            address,
+            job: Job::Idle,
+            first: None,
+            packets: None,
+            wait: 0,
+            next: None,
+        }
+    }
+
+    /// Release the I2C peripheral
+    pub fn release(self) -> I2C {
+        self.i2c
+    }
+
+    /// Whether the player has nothing left to write
+    pub fn is_idle(&self) -> bool {
+        self.packets.is_none() && self.first.is_none() && matches!(self.job, Job::Idle)
+    }
+
+    /// Abandon the current job, once the command being written is complete
+    pub fn stop(&mut self) {
+        self.job = Job::Idle;
+        self.first = None;
+    }
+
+    /// Write any command
+    ///
+    /// Starting a job abandons the previous one, once the command being
+    /// written is complete. Commands the driver would refuse are refused
+    /// when the job is started, the previous one going on.
+    pub fn send(&mut self, command: Command) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+        self.start(Job::Command(command))
+    }
+
+    /// Upload and display frames, see
+    /// [`My9221LedMatrix::display_frames`]
+    ///
+    /// # Arguments
+    ///
+    /// * `frames` - The frames to display
+    /// * `playback` - How long to display the frames
+    /// * `frames_number` - The total number of frames
+    ///
+    pub fn display_frames(
+        &mut self,
+        frames: &'a [Frame],
+        playback: Playback,
+        frames_number: u8,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+        let frames_number = frames_number.min(5);
+        if frames_number == 0 || frames.len() < frames_number as usize {
+            return Err(My9221LedMatrixError::InvalidArgument);
+        }
+        self.start(Job::Frames {
+            frames,
+            remaining: frames_number,
+            frames_number,
+            playback,
+        })
+    }
+
+    /// Display a string, see
+    /// [`My9221LedMatrix::display_string`]
+    ///
+    /// # Arguments
+    ///
+    /// * `string` - The string to display
+    /// * `playback` - How long to display the string
+    /// * `color` - The color of the string, a [`Colors`](crate::Colors) or any
+    ///   color convertible to a [`Hue`]
+    ///
+    pub fn display_string<C>(
+        &mut self,
+        string: &'a str,
+        playback: Playback,
+        color: C,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
+    where
+        C: Into<Hue>,
+    {
+        if let Some(c) = string.chars().find(|&c| !protocol::is_supported(c)) {
+            return Err(My9221LedMatrixError::UnsupportedChar(c));
+        }
+        self.display_string_transliterated(string, playback, color)
+    }
+
+    /// Display a string, replacing the characters which aren't supported by
+    /// the firmware font with the closest supported ones
+    ///
+    /// # Arguments
+    ///
+    /// * `string` - The string to display
+    /// * `playback` - How long to display the string
+    /// * `color` - The color of the string, a [`Colors`](crate::Colors) or any
+    ///   color convertible to a [`Hue`]
+    ///
+    pub fn display_string_transliterated<C>(
+        &mut self,
+        string: &'a str,
+        playback: Playback,
+        color: C,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
+    where
+        C: Into<Hue>,
+    {
+        let transliterate: fn(char) -> Chars<'static> = |c| protocol::transliterate(c).chars();
+        let texts = Texts::new(string.chars().flat_map(transliterate), playback)
+            .ok_or(My9221LedMatrixError::InvalidArgument)?;
+        self.start(Job::String {
+            texts: texts.peekable(),
+            color: color.into(),
+        })
+    }
+
+    /// Store the frames of the internal buffer in flash
+    pub fn store_frames(&mut self) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+        self.send(Command::StoreFlash)
+    }
+
+    /// Delete the frames stored in flash
+    pub fn delete_frames(&mut self) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+        self.send(Command::DeleteFlash)
+    }
+
+    /// Play the keyframes of an animation, see
+    /// [`My9221LedMatrix::play_animation`]
+    pub fn play_animation(
+        &mut self,
+        animation: &Animation<'a>,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+        self.start(Job::Animation(animation.steps()))
+    }
+
+    /// Write the next packet if it is time to
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - The current time in ms
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Option<u32>, My9221LedMatrixError<I2C::Error>>` - Returns
+    ///   when to poll again, or `None` when the player is idle. The job is
+    ///   abandoned on I2C errors.
+    ///
+    pub fn poll(&mut self, now: u32) -> Result<Option<u32>, My9221LedMatrixError<I2C::Error>> {
+        if let Some(next) = self.next {
+            // Compared as a difference, so the counter may wrap around
+            if (now.wrapping_sub(next) as i32) < 0 {
+                return Ok((!self.is_idle()).then_some(next));
+            }
+            self.next = None;
+        }
+        if self.packets.is_none() && !self.start_command() {
+            return Ok(None);
+        }
+        let Some(packets) = self.packets.as_mut() else {
+            return Ok(None);
+        };
+        // Commands have at least one packet, and are dropped once all of
+        // them are written
+        let Some(packet) = packets.next() else {
+            return Ok(None);
+        };
+        let done = packets.peek().is_none();
+
+        if let Err(e) = self.i2c.write(self.address, packet.as_bytes()) {
+            self.packets = None;
+            self.stop();
+            return Err(My9221LedMatrixError::I2c(e));
+        }
+
+        let mut delay = packet.delay_ms;
+        if done {
+            self.packets = None;
+            delay += self.wait;
+            self.start_command();
+        }
+        if delay > 0 {
+            self.next = Some(now.wrapping_add(delay));
+        }
+        Ok(if self.is_idle() {
+            None
+        } else {
+            Some(self.next.unwrap_or(now))
+        })
+    }
+
+    /// Replace the current job, once the driver accepts the command it
+    /// starts with
+    ///
+    /// The commands of a job are all alike, so they are only checked once.
+    fn start(&mut self, mut job: Job<'a>) -> Result<(), My9221LedMatrixError<I2C::Error>> {
+        let first = job.next();
+        if let Some((command, _)) = &first {
+            My9221LedMatrix::check(command)?;
+        }
+        self.job = job;
+        self.first = first;
+        Ok(())
+    }
+
+    /// Take the next command of the job, returning `false` if there is none
+    fn start_command(&mut self) -> bool {
+        match self.first.take().or_else(|| self.job.next()) {
+            Some((command, wait)) => {
+                self.packets = Some(command.packets().peekable());
+                self.wait = wait;
+                true
+            }
+            None => {
+                self.job = Job::Idle;
+                false
+            }
+        }
+    }
+}
@@ -0,0 +1,970 @@
+//! Encoding of the commands understood by the device
+//!
+//! A [`Command`] is turned into a sequence of [`Packet`]s to write to the
+//! device, independently of the bus used to send them. Commands carrying more
+//! data than the device accepts in a single write are split in continuation
+//! packets, each packet tells how long to wait before sending the next one.
+//!
+//! # Example
+//!
+//! ```
+//!    use grove_matrix_led_my9221_rs::{protocol::Command, Emojis, Playback};
+//!
+//!    let command = Command::DisplayEmoji {
+//!        emoji: Emojis::Heart,
+//!        playback: Playback::Forever,
+//!    };
+//!
+//!    let mut packets = command.packets();
+//!    let packet = packets.next().unwrap();
+//!    assert_eq!(packet.as_bytes(), &[0x02, 0x0a, 0x00, 0x00, 0x01]);
+//!    assert_eq!(packet.delay_ms, 0);
+//!    assert!(packets.next().is_none());
+//! ```
+
+use core::fmt;
+
+use crate::{DisplayRotate, Emojis, Frame, I2cCmd, Millis, Playback};
+
+/// Maximum number of characters of a string displayed by the device
+pub const MAX_STRING_LEN: usize = 28;
+
+/// Maximum length of a packet written to the device
+pub const MAX_PACKET_LEN: usize = 31;
+
+/// Maximum length of an encoded command, before being split in packets
+const MAX_COMMAND_LEN: usize = 72;
+
+/// Characters of the firmware font, from `' '` to `'~'`
+const PRINTABLE: &str = " !\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_`abcdefghijklmnopqrstuvwxyz{|}~";
+
+/// Whether the firmware font has a glyph for a character, only printable
+/// ASCII characters are supported
+pub fn is_supported(c: char) -> bool {
+    matches!(c, ' '..='~')
+}
+
+/// The closest characters supported by the firmware font
+///
+/// Supported characters are kept, accented latin letters lose their accent,
+/// ligatures and typographic punctuation are spelled in ASCII and anything
+/// else becomes `'?'`.
+///
+/// ```
+///    use grove_matrix_led_my9221_rs::protocol::transliterate;
+///
+///    assert_eq!(transliterate('a'), "a");
+///    assert_eq!(transliterate('é'), "e");
+///    assert_eq!(transliterate('ß'), "ss");
+///    assert_eq!(transliterate('…'), "...");
+///    assert_eq!(transliterate('😀'), "?");
+/// ```
+pub fn transliterate(c: char) -> &'static str {
+    if is_supported(c) {
+        let index = c as usize - ' ' as usize;
+        return &PRINTABLE[index..index + 1];
+    }
+    match c {
+        'À'..='Å' | 'Ā' | 'Ă' | 'Ą' => "A",
+        'à'..='å' | 'ā' | 'ă' | 'ą' => "a",
+        'Æ' => "AE",
+        'æ' => "ae",
+        'Ç' | 'Ć' | 'Ĉ' | 'Ċ' | 'Č' => "C",
+        'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => "c",
+        'Ď' | 'Đ' | 'Ð' => "D",
+        'ď' | 'đ' | 'ð' => "d",
+        'È'..='Ë' | 'Ē' | 'Ĕ' | 'Ė' | 'Ę' | 'Ě' => "E",
+        'è'..='ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => "e",
+        'Ĝ' | 'Ğ' | 'Ġ' | 'Ģ' => "G",
+        'ĝ' | 'ğ' | 'ġ' | 'ģ' => "g",
+        'Ì'..='Ï' | 'Ī' | 'Ĭ' | 'Į' | 'İ' => "I",
+        'ì'..='ï' | 'ī' | 'ĭ' | 'į' | 'ı' => "i",
+        'Ł' => "L",
+        'ł' => "l",
+        'Ñ' | 'Ń' | 'Ň' => "N",
+        'ñ' | 'ń' | 'ň' => "n",
+        'Ò'..='Ö' | 'Ø' | 'Ō' | 'Ŏ' | 'Ő' => "O",
+        'ò'..='ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => "o",
+        'Œ' => "OE",
+        'œ' => "oe",
+        'Ř' => "R",
+        'ř' => "r",
+        'Ś' | 'Ş' | 'Š' => "S",
+        'ś' | 'ş' | 'š' => "s",
+        'ß' => "ss",
+        'Ţ' | 'Ť' => "T",
+        'ţ' | 'ť' => "t",
+        'Ù'..='Ü' | 'Ū' | 'Ŭ' | 'Ů' | 'Ű' | 'Ų' => "U",
+        'ù'..='ü' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => "u",
+        'Ý' | 'Ÿ' => "Y",
+        'ý' | 'ÿ' => "y",
+        'Ź' | 'Ż' | 'Ž' => "Z",
+        'ź' | 'ż' | 'ž' => "z",
+        '\u{a0}' | '\t' | '\n' | '\r' => " ",
+        '‘' | '’' | '‚' | '′' => "'",
+        '“' | '”' | '„' | '″' | '«' | '»' => "\"",
+        '‐'..='—' | '−' => "-",
+        '…' => "...",
+        '×' => "x",
+        '÷' => "/",
+        '·' | '•' => ".",
+        _ => "?",
+    }
+}
+
+/// A string of at most [`MAX_STRING_LEN`] characters, as sent to the device
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Text {
+    bytes: [u8; MAX_STRING_LEN],
+    len: u8,
+}
+
+impl Text {
+    /// Create a text from a string, truncated to [`MAX_STRING_LEN`]
+    /// characters, unsupported characters are replaced by `'?'`
+    pub fn new(string: &str) -> Self {
+        Self::from_chars(&mut string.chars())
+    }
+
+    /// Create a text from the next [`MAX_STRING_LEN`] characters
+    fn from_chars(chars: &mut impl Iterator<Item = char>) -> Self {
+        let mut bytes = [0; MAX_STRING_LEN];
+        let mut len = 0;
+        for (byte, c) in bytes.iter_mut().zip(chars) {
+            *byte = if is_supported(c) { c as u8 } else { b'?' };
+            len += 1;
+        }
+        Self { bytes, len }
+    }
+
+    /// The characters of the text, one byte each
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.len as usize]
+    }
+}
+
+impl fmt::Debug for Text {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "\"{}\"", self)
+    }
+}
+
+impl fmt::Display for Text {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_bytes()
+            .iter()
+            .try_for_each(|&b| fmt::Write::write_char(f, char::from(b)))
+    }
+}
+
+/// Iterator splitting characters in [`Text`]s displayed one after the other
+///
+/// Each text is displayed for a share of the playback duration proportional
+/// to its length. A string shorter than [`MAX_STRING_LEN`] is a single text,
+/// even if empty.
+#[derive(Debug, Clone)]
+pub struct Texts<I> {
+    chars: I,
+    len: usize,
+    sent: usize,
+    duration: Option<u16>,
+    done: bool,
+}
+
+impl<I> Texts<I>
+where
+    I: Iterator<Item = char> + Clone,
+{
+    /// Split characters, returning `None` if they don't fit in a single text
+    /// while played forever, since the device can't loop over several texts
+    pub fn new(chars: I, playback: Playback) -> Option<Self> {
+        let len = chars.clone().count();
+        let duration = match playback {
+            Playback::Once(duration) => Some(duration.as_ms()),
+            Playback::Forever if len <= MAX_STRING_LEN => None,
+            Playback::Forever => return None,
+        };
+        Some(Self {
+            chars,
+            len,
+            sent: 0,
+            duration,
+            done: false,
+        })
+    }
+}
+
+impl<I> Iterator for Texts<I>
+where
+    I: Iterator<Item = char>,
+{
+    type Item = (Text, Playback);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let text = Text::from_chars(&mut self.chars);
+        let start = self.sent;
+        self.sent += text.len as usize;
+        self.done = self.sent >= self.len;
+
+        let playback = match self.duration {
+            Some(duration) => {
+                // Time elapsed once `sent` characters are displayed, so that
+                // the shares add up to the whole duration
+                let elapsed = |sent: usize| {
+                    if sent >= self.len {
+                        duration
+                    } else {
+                        (duration as u64 * sent as u64 / self.len as u64) as u16
+                    }
+                };
+                let start = if start == 0 { 0 } else { elapsed(start) };
+                Playback::Once(Millis::new(elapsed(self.sent) - start))
+            }
+            None => Playback::Forever,
+        };
+        Some((text, playback))
+    }
+}
+
+/// A command understood by the device
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    /// Get the device ID information
+    GetDeviceId,
+    /// Display a bar
+    DisplayBar {
+        bar: u8,
+        playback: Playback,
+        color: u8,
+    },
+    /// Display a built-in emoji
+    DisplayEmoji { emoji: Emojis, playback: Playback },
+    /// Display a number
+    DisplayNumber {
+        number: u16,
+        playback: Playback,
+        color: u8,
+    },
+    /// Display a string
+    DisplayString {
+        text: Text,
+        playback: Playback,
+        color: u8,
+    },
+    /// Upload the frame at `index` out of `frames_number` user-defined frames
+    ///
+    /// Frames are expected from the last one to the first one: receiving
+    /// the frame at index 0 starts the display, so `playback` is only sent
+    /// along with it and ignored for the other frames.
+    DisplayCustom {
+        frame: Frame,
+        index: u8,
+        frames_number: u8,
+        playback: Playback,
+    },
+    /// Stop the display
+    DisplayOff,
+    /// Display frames stored in flash, indexes start at 1
+    DisplayFlash {
+        playback: Playback,
+        from_idx: u8,
+        to_idx: u8,
+    },
+    /// Display a color bar
+    DisplayColorBar { bar: u8, playback: Playback },
+    /// Display the built-in wave animation
+    DisplayColorWave { wave: u8, playback: Playback },
+    /// Display the built-in clockwise animation
+    DisplayColorClockwise {
+        clockwise: bool,
+        big: bool,
+        playback: Playback,
+    },
+    /// Display the built-in images between two indexes as an animation
+    DisplayColorAnimation {
+        from_idx: u8,
+        to_idx: u8,
+        playback: Playback,
+    },
+    /// Display a color block in RGB format (0x00RRGGBB)
+    DisplayColorBlock { rgb: u32, playback: Playback },
+    /// Store the user-defined frames in flash
+    StoreFlash,
+    /// Delete all the frames stored in flash
+    DeleteFlash,
+    /// Turn on the indicator LED flash mode
+    LedFlashOn,
+    /// Turn off the indicator LED flash mode
+    LedFlashOff,
+    /// Enable auto sleep mode
+    AutoSleepOn,
+    /// Disable auto sleep mode
+    AutoSleepOff,
+    /// Set the display orientation
+    Rotate(DisplayRotate),
+    /// Set the display offset (x, y)
+    Offset(u8, u8),
+    /// Set the I2C address of the device
+    SetAddress(u8),
+    /// Reset the I2C address of the device to its default value
+    ResetAddress,
+    /// Enable TX RX pin test mode
+    TestModeOn,
+    /// Disable TX RX pin test mode
+    TestModeOff,
+    /// Get the software version
+    GetVersion,
+    /// Get the chip UID
+    GetDeviceUid,
+}
+
+/// A packet to write to the device
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Packet {
+    buf: [u8; MAX_PACKET_LEN],
+    len: u8,
+    /// Time to wait after writing the packet, in ms
+    pub delay_ms: u32,
+}
+
+impl Packet {
+    /// The bytes to write to the device
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buf[..self.len as usize]
+    }
+}
+
+/// Iterator over the packets of a command
+pub struct Packets {
+    buf: [u8; MAX_COMMAND_LEN],
+    len: usize,
+    offset: usize,
+    chunk_len: usize,
+    first_delay_ms: u32,
+    last_delay_ms: u32,
+}
+
+impl Iterator for Packets {
+    type Item = Packet;
+
+    fn next(&mut self) -> Option<Packet> {
+        if self.offset >= self.len {
+            return None;
+        }
+
+        let mut packet = Packet {
+            buf: [0; MAX_PACKET_LEN],
+            len: 0,
+            delay_ms: 0,
+        };
+        let first = self.offset == 0;
+        let start = if first {
+            0
+        } else {
+            packet.buf[0] = I2cCmd::ContinueData as u8;
+            1
+        };
+        let chunk_len = (self.len - self.offset)
+            .min(self.chunk_len)
+            .min(MAX_PACKET_LEN - start);
+        packet.buf[start..start + chunk_len]
+            .copy_from_slice(&self.buf[self.offset..self.offset + chunk_len]);
+        packet.len = (start + chunk_len) as u8;
+        self.offset += chunk_len;
+
+        packet.delay_ms = if self.offset >= self.len {
+            self.last_delay_ms
+        } else if first {
+            self.first_delay_ms
+        } else {
+            0
+        };
+        Some(packet)
+    }
+}
+
+impl Command {
+    /// Split the command in the packets to write to the device
+    pub fn packets(&self) -> Packets {
+        let mut buf = [0; MAX_COMMAND_LEN];
+        let mut len = self.encode(&mut buf);
+        if let Command::DisplayString { .. } = self {
+            // The rest of a long string is always sent in a continuation
+            // packet of 6 bytes, padded with zeros
+            if len > MAX_PACKET_LEN {
+                len = MAX_PACKET_LEN + 5;
+            }
+        }
+        let (chunk_len, first_delay_ms, last_delay_ms) = match self {
+            Command::DisplayString { .. } => (31, 1, 0),
+            Command::DisplayCustom { .. } => (24, 10, 0),
+            // The address is stored in flash as well
+            Command::StoreFlash
+            | Command::DeleteFlash
+            | Command::SetAddress(_)
+            | Command::ResetAddress => (len, 0, 200),
+            _ => (len, 0, 0),
+        };
+        Packets {
+            buf,
+            len,
+            offset: 0,
+            chunk_len,
+            first_delay_ms,
+            last_delay_ms,
+        }
+    }
+
+    /// Number of bytes the device answers to the command
+    pub fn response_len(&self) -> usize {
+        match self {
+            Command::GetDeviceId | Command::GetDeviceUid => 1,
+            Command::GetVersion => 4,
+            _ => 0,
+        }
+    }
+
+    /// Encode the whole command, returning its length
+    ///
+    /// The forever flag is inverted for the custom frames, the frames stored
+    /// in flash and the built-in color animations, which send 0 to display
+    /// forever.
+    fn encode(&self, buf: &mut [u8; MAX_COMMAND_LEN]) -> usize {
+        let inverted_flag = |value: bool| if value { 0 } else { 1 };
+
+        match *self {
+            Command::GetDeviceId => put(buf, I2cCmd::GetDevID, &[]),
+            Command::DisplayBar {
+                bar,
+                playback,
+                color,
+            } => {
+                let ([lo, hi], forever) = playback.encode(false);
+                put(buf, I2cCmd::DispBar, &[bar, lo, hi, forever, color])
+            }
+            Command::DisplayEmoji { emoji, playback } => {
+                let ([lo, hi], forever) = playback.encode(false);
+                put(buf, I2cCmd::DispEmoji, &[emoji as u8, lo, hi, forever])
+            }
+            Command::DisplayNumber {
+                number,
+                playback,
+                color,
+            } => {
+                let [n_lo, n_hi] = number.to_le_bytes();
+                let ([lo, hi], forever) = playback.encode(false);
+                put(buf, I2cCmd::DispNum, &[n_lo, n_hi, lo, hi, forever, color])
+            }
+            Command::DisplayString {
+                text,
+                playback,
+                color,
+            } => {
+                let bytes = text.as_bytes();
+                let (duration, forever) = playback.encode(false);
+                buf[0] = I2cCmd::DispStr as u8;
+                buf[1] = forever;
+                buf[2..4].copy_from_slice(&duration);
+                buf[4] = bytes.len() as u8;
+                buf[5] = color;
+                buf[6..6 + bytes.len()].copy_from_slice(bytes);
+                6 + bytes.len()
+            }
+            Command::DisplayCustom {
+                frame,
+                index,
+                frames_number,
+                playback,
+            } => {
+                buf[0] = I2cCmd::DispCustom as u8;
+                if index == 0 {
+                    let (duration, forever) = playback.encode(true);
+                    buf[1..3].copy_from_slice(&duration);
+                    buf[3] = forever;
+                }
+                buf[4] = frames_number;
+                buf[5] = index;
+                buf[8..72].copy_from_slice(&frame.data);
+                72
+            }
+            Command::DisplayOff => put(buf, I2cCmd::DispOff, &[]),
+            Command::DisplayFlash {
+                playback,
+                from_idx,
+                to_idx,
+            } => {
+                let ([lo, hi], forever) = playback.encode(true);
+                put(buf, I2cCmd::DispFlash, &[lo, hi, forever, from_idx, to_idx])
+            }
+            Command::DisplayColorBar { bar, playback } => {
+                let ([lo, hi], forever) = playback.encode(true);
+                put(buf, I2cCmd::DispColorBar, &[bar, lo, hi, forever])
+            }
+            Command::DisplayColorWave { wave, playback } => {
+                let ([lo, hi], forever) = playback.encode(true);
+                put(buf, I2cCmd::DispColorWave, &[wave, lo, hi, forever])
+            }
+            Command::DisplayColorClockwise {
+                clockwise,
+                big,
+                playback,
+            } => {
+                let ([lo, hi], forever) = playback.encode(true);
+                put(
+                    buf,
+                    I2cCmd::DispColorClockWise,
+                    &[
+                        inverted_flag(clockwise),
+                        inverted_flag(big),
+                        lo,
+                        hi,
+                        forever,
+                    ],
+                )
+            }
+            Command::DisplayColorAnimation {
+                from_idx,
+                to_idx,
+                playback,
+            } => {
+                let ([lo, hi], forever) = playback.encode(true);
+                put(
+                    buf,
+                    I2cCmd::DispColorAnimation,
+                    &[from_idx, to_idx, lo, hi, forever],
+                )
+            }
+            Command::DisplayColorBlock { rgb, playback } => {
+                let [_, r, g, b] = rgb.to_be_bytes();
+                let ([lo, hi], forever) = playback.encode(true);
+                put(buf, I2cCmd::DispColorBlock, &[r, g, b, lo, hi, forever])
+            }
+            Command::StoreFlash => put(buf, I2cCmd::StoreFlash, &[]),
+            Command::DeleteFlash => put(buf, I2cCmd::DeleteFlash, &[]),
+            Command::LedFlashOn => put(buf, I2cCmd::LedOn, &[]),
+            Command::LedFlashOff => put(buf, I2cCmd::LedOff, &[]),
+            Command::AutoSleepOn => put(buf, I2cCmd::AutoSleepOn, &[]),
+            Command::AutoSleepOff => put(buf, I2cCmd::AutoSleepOff, &[]),
+            Command::Rotate(rotate) => put(buf, I2cCmd::DispRotate, &[rotate as u8]),
+            Command::Offset(x, y) => put(buf, I2cCmd::DispOffset, &[x, y]),
+            Command::SetAddress(address) => put(buf, I2cCmd::SetAddress, &[address]),
+            Command::ResetAddress => put(buf, I2cCmd::ResetAddress, &[]),
+            Command::TestModeOn => put(buf, I2cCmd::TestTXRXOn, &[]),
+            Command::TestModeOff => put(buf, I2cCmd::TestTXRXOff, &[]),
+            Command::GetVersion => put(buf, I2cCmd::TestGetVersion, &[]),
+            Command::GetDeviceUid => put(buf, I2cCmd::GetDeviceUID, &[]),
+        }
+    }
+}
+
+/// Write an opcode and its arguments, returning the length of the command
+fn put(buf: &mut [u8; MAX_COMMAND_LEN], cmd: I2cCmd, args: &[u8]) -> usize {
+    buf[0] = cmd as u8;
+    buf[1..1 + args.len()].copy_from_slice(args);
+    1 + args.len()
+}
+
+/// The errors that can occur when decoding packets
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The packet is empty
+    Empty,
+    /// The opcode is not a command understood by the device
+    UnknownOpcode(u8),
+    /// The command does not have the expected length
+    InvalidLength { opcode: u8, len: usize },
+    /// An argument of the command has a value unknown to the device
+    InvalidArgument { opcode: u8, value: u8 },
+    /// Continuation data was received while no command was expecting it
+    UnexpectedContinuation,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DecodeError::Empty => write!(f, "Empty packet"),
+            DecodeError::UnknownOpcode(opcode) => write!(f, "Unknown opcode {:#04x}", opcode),
+            DecodeError::InvalidLength { opcode, len } => {
+                write!(f, "Invalid length {} for opcode {:#04x}", len, opcode)
+            }
+            DecodeError::InvalidArgument { opcode, value } => {
+                write!(
+                    f,
+                    "Invalid argument {:#04x} for opcode {:#04x}",
+                    value, opcode
+                )
+            }
+            DecodeError::UnexpectedContinuation => write!(f, "Unexpected continuation data"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecodeError {}
+
+impl Command {
+    /// Decode a complete command, after reassembly of its continuation
+    /// packets
+    ///
+    /// Use a [`Decoder`] to decode the packets written on the bus.
+    pub fn decode(bytes: &[u8]) -> Result<Command, DecodeError> {
+        let (&opcode, args) = bytes.split_first().ok_or(DecodeError::Empty)?;
+        let cmd = I2cCmd::try_from(opcode).map_err(DecodeError::UnknownOpcode)?;
+
+        let expected_len = match cmd {
+            I2cCmd::DispBar => 5,
+            I2cCmd::DispEmoji => 4,
+            I2cCmd::DispNum => 6,
+            I2cCmd::DispStr => match args.get(3) {
+                Some(&len) if len as usize <= MAX_STRING_LEN => 5 + len as usize,
+                Some(&len) => return Err(DecodeError::InvalidArgument { opcode, value: len }),
+                None => 5,
+            },
+            I2cCmd::DispCustom => 71,
+            I2cCmd::DispFlash | I2cCmd::DispColorClockWise | I2cCmd::DispColorAnimation => 5,
+            I2cCmd::DispColorBar | I2cCmd::DispColorWave => 4,
+            I2cCmd::DispColorBlock => 6,
+            I2cCmd::DispRotate | I2cCmd::SetAddress => 1,
+            I2cCmd::DispOffset => 2,
+            I2cCmd::DispAscii | I2cCmd::ContinueData => {
+                return Err(DecodeError::UnknownOpcode(opcode))
+            }
+            _ => 0,
+        };
+        if args.len() != expected_len {
+            return Err(DecodeError::InvalidLength {
+                opcode,
+                len: bytes.len(),
+            });
+        }
+
+        let inverted_flag = |value: u8| value == 0;
+        // The custom frames, the frames stored in flash and the built-in color
+        // animations send 0 to display forever
+        let inverted = matches!(
+            cmd,
+            I2cCmd::DispCustom
+                | I2cCmd::DispFlash
+                | I2cCmd::DispColorBar
+                | I2cCmd::DispColorWave
+                | I2cCmd::DispColorClockWise
+                | I2cCmd::DispColorAnimation
+                | I2cCmd::DispColorBlock
+        );
+        let playback = |lo: usize, forever: usize| {
+            Playback::decode([args[lo], args[lo + 1]], args[forever], inverted)
+        };
+
+        Ok(match cmd {
+            I2cCmd::GetDevID => Command::GetDeviceId,
+            I2cCmd::DispBar => Command::DisplayBar {
+                bar: args[0],
+                playback: playback(1, 3),
+                color: args[4],
+            },
+            I2cCmd::DispEmoji => {
+                if args[0] > Emojis::Smile as u8 {
+                    return Err(DecodeError::InvalidArgument {
+                        opcode,
+                        value: args[0],
+                    });
+                }
+                Command::DisplayEmoji {
+                    emoji: Emojis::from(args[0]),
+                    playback: playback(1, 3),
+                }
+            }
+            I2cCmd::DispNum => Command::DisplayNumber {
+                number: u16::from_le_bytes([args[0], args[1]]),
+                playback: playback(2, 4),
+                color: args[5],
+            },
+            I2cCmd::DispStr => {
+                let mut text = Text {
+                    bytes: [0; MAX_STRING_LEN],
+                    len: args[3],
+                };
+                text.bytes[..args[3] as usize].copy_from_slice(&args[5..]);
+                Command::DisplayString {
+                    text,
+                    playback: playback(1, 0),
+                    color: args[4],
+                }
+            }
+            I2cCmd::DispCustom => {
+                let mut frame = Frame { data: [0; 64] };
+                frame.data.copy_from_slice(&args[7..]);
+                Command::DisplayCustom {
+                    frame,
+                    index: args[4],
+                    frames_number: args[3],
+                    playback: playback(0, 2),
+                }
+            }
+            I2cCmd::DispOff => Command::DisplayOff,
+            I2cCmd::DispFlash => Command::DisplayFlash {
+                playback: playback(0, 2),
+                from_idx: args[3],
+                to_idx: args[4],
+            },
+            I2cCmd::DispColorBar => Command::DisplayColorBar {
+                bar: args[0],
+                playback: playback(1, 3),
+            },
+            I2cCmd::DispColorWave => Command::DisplayColorWave {
+                wave: args[0],
+                playback: playback(1, 3),
+            },
+            I2cCmd::DispColorClockWise => Command::DisplayColorClockwise {
+                clockwise: inverted_flag(args[0]),
+                big: inverted_flag(args[1]),
+                playback: playback(2, 4),
+            },
+            I2cCmd::DispColorAnimation => Command::DisplayColorAnimation {
+                from_idx: args[0],
+                to_idx: args[1],
+                playback: playback(2, 4),
+            },
+            I2cCmd::DispColorBlock => Command::DisplayColorBlock {
+                rgb: u32::from_be_bytes([0, args[0], args[1], args[2]]),
+                playback: playback(3, 5),
+            },
+            I2cCmd::StoreFlash => Command::StoreFlash,
+            I2cCmd::DeleteFlash => Command::DeleteFlash,
+            I2cCmd::LedOn => Command::LedFlashOn,
+            I2cCmd::LedOff => Command::LedFlashOff,
+            I2cCmd::AutoSleepOn => Command::AutoSleepOn,
+            I2cCmd::AutoSleepOff => Command::AutoSleepOff,
+            I2cCmd::DispRotate => Command::Rotate(match args[0] {
+                0 => DisplayRotate::Deg0,
+                1 => DisplayRotate::Deg90,
+                2 => DisplayRotate::Deg180,
+                3 => DisplayRotate::Deg270,
+                value => return Err(DecodeError::InvalidArgument { opcode, value }),
+            }),
+            I2cCmd::DispOffset => Command::Offset(args[0], args[1]),
+            I2cCmd::SetAddress => Command::SetAddress(args[0]),
+            I2cCmd::ResetAddress => Command::ResetAddress,
+            I2cCmd::TestTXRXOn => Command::TestModeOn,
+            I2cCmd::TestTXRXOff => Command::TestModeOff,
+            I2cCmd::TestGetVersion => Command::GetVersion,
+            I2cCmd::GetDeviceUID => Command::GetDeviceUid,
+            I2cCmd::DispAscii | I2cCmd::ContinueData => unreachable!(),
+        })
+    }
+}
+
+/// A human-readable description of the command
+impl fmt::Display for Command {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Command::GetDeviceId => write!(f, "get device ID"),
+            Command::DisplayBar {
+                bar,
+                playback,
+                color,
+            } => write!(
+                f,
+                "display bar {} in color {:#04x} {}",
+                bar, color, playback
+            ),
+            Command::DisplayEmoji { emoji, playback } => {
+                write!(f, "display emoji {:?} {}", emoji, playback)
+            }
+            Command::DisplayNumber {
+                number,
+                playback,
+                color,
+            } => write!(
+                f,
+                "display number {} in color {:#04x} {}",
+                number, color, playback
+            ),
+            Command::DisplayString {
+                text,
+                playback,
+                color,
+            } => write!(
+                f,
+                "display string {:?} in color {:#04x} {}",
+                text, color, playback
+            ),
+            Command::DisplayCustom {
+                index,
+                frames_number,
+                playback,
+                ..
+            } => {
+                write!(f, "upload frame {} of {}", index + 1, frames_number)?;
+                if index == 0 {
+                    write!(f, ", display frames {}", playback)?;
+                }
+                Ok(())
+            }
+            Command::DisplayOff => write!(f, "stop display"),
+            Command::DisplayFlash {
+                playback,
+                from_idx,
+                to_idx,
+            } => write!(
+                f,
+                "display frames {} to {} from flash {}",
+                from_idx, to_idx, playback
+            ),
+            Command::DisplayColorBar { bar, playback } => {
+                write!(f, "display color bar {} {}", bar, playback)
+            }
+            Command::DisplayColorWave { wave, playback } => {
+                write!(f, "display color wave {} {}", wave, playback)
+            }
+            Command::DisplayColorClockwise {
+                clockwise,
+                big,
+                playback,
+            } => write!(
+                f,
+                "display {} {} animation {}",
+                if big { "big" } else { "small" },
+                if clockwise {
+                    "clockwise"
+                } else {
+                    "anti-clockwise"
+                },
+                playback
+            ),
+            Command::DisplayColorAnimation {
+                from_idx,
+                to_idx,
+                playback,
+            } => write!(
+                f,
+                "display color animation from image {} to {} {}",
+                from_idx, to_idx, playback
+            ),
+            Command::DisplayColorBlock { rgb, playback } => {
+                write!(f, "display color block #{:06x} {}", rgb, playback)
+            }
+            Command::StoreFlash => write!(f, "store frames in flash"),
+            Command::DeleteFlash => write!(f, "delete frames from flash"),
+            Command::LedFlashOn => write!(f, "turn on indicator LED flash mode"),
+            Command::LedFlashOff => write!(f, "turn off indicator LED flash mode"),
+            Command::AutoSleepOn => write!(f, "enable auto sleep mode"),
+            Command::AutoSleepOff => write!(f, "disable auto sleep mode"),
+            Command::Rotate(rotate) => write!(f, "rotate display {:?}", rotate),
+            Command::Offset(x, y) => write!(f, "set display offset ({}, {})", x, y),
+            Command::SetAddress(address) => write!(f, "set address {:#04x}", address),
+            Command::ResetAddress => write!(f, "reset address"),
+            Command::TestModeOn => write!(f, "enable TX RX pin test mode"),
+            Command::TestModeOff => write!(f, "disable TX RX pin test mode"),
+            Command::GetVersion => write!(f, "get version"),
+            Command::GetDeviceUid => write!(f, "get device UID"),
+        }
+    }
+}
+
+/// Decoder of the packets written to the device, reassembling the commands
+/// split in continuation packets
+///
+/// # Example
+///
+/// ```
+///    use grove_matrix_led_my9221_rs::protocol::{Command, Decoder};
+///
+///    let mut decoder = Decoder::new();
+///
+///    let command = decoder.feed(&[0xb5, 0x01, 0x02]).unwrap();
+///    assert_eq!(command, Some(Command::Offset(1, 2)));
+///
+///    // A string of 27 characters is split in two packets
+///    let mut first = [b'a'; 31];
+///    first[..6].copy_from_slice(&[0x04, 0x01, 0x00, 0x00, 27, 0x00]);
+///    assert_eq!(decoder.feed(&first).unwrap(), None);
+///    let command = decoder.feed(&[0x81, b'b', b'c']).unwrap().unwrap();
+///    assert_eq!(
+///        command.to_string(),
+///        "display string \"aaaaaaaaaaaaaaaaaaaaaaaaabc\" in color 0x00 forever"
+///    );
+/// ```
+pub struct Decoder {
+    buf: [u8; MAX_COMMAND_LEN],
+    len: usize,
+    expected_len: usize,
+}
+
+impl Default for Decoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Decoder {
+    /// Create a decoder waiting for a new command
+    pub fn new() -> Self {
+        Self {
+            buf: [0; MAX_COMMAND_LEN],
+            len: 0,
+            expected_len: 0,
+        }
+    }
+
+    /// Whether the decoder is waiting for continuation packets
+    pub fn is_pending(&self) -> bool {
+        self.expected_len > 0
+    }
+
+    /// Decode a packet written to the device
+    ///
+    /// Returns `None` while the command is waiting for continuation packets.
+    /// An unfinished command is dropped when another command starts, as
+    /// the device does. Continuation data exceeding the length of the
+    /// command is ignored.
+    pub fn feed(&mut self, packet: &[u8]) -> Result<Option<Command>, DecodeError> {
+        let (&opcode, data) = packet.split_first().ok_or(DecodeError::Empty)?;
+
+        if opcode == I2cCmd::ContinueData as u8 {
+            if !self.is_pending() {
+                return Err(DecodeError::UnexpectedContinuation);
+            }
+            let len = data.len().min(self.expected_len - self.len);
+            self.buf[self.len..self.len + len].copy_from_slice(&data[..len]);
+            self.len += len;
+        } else {
+            let expected_len = match opcode {
+                o if o == I2cCmd::DispStr as u8 => match packet.get(4) {
+                    Some(&len) => 6 + (len as usize).min(MAX_STRING_LEN),
+                    None => packet.len(),
+                },
+                o if o == I2cCmd::DispCustom as u8 => MAX_COMMAND_LEN,
+                // No command is longer than the buffer
+                _ => packet.len().min(MAX_COMMAND_LEN),
+            };
+            if packet.len() > expected_len {
+                // Trailing bytes are only allowed in continuation packets
+                self.reset();
+                return Err(DecodeError::InvalidLength {
+                    opcode,
+                    len: packet.len(),
+                });
+            }
+            self.buf[..packet.len()].copy_from_slice(packet);
+            self.len = packet.len();
+            self.expected_len = expected_len;
+        }
+
+        if self.len < self.expected_len {
+            return Ok(None);
+        }
+        let command = Command::decode(&self.buf[..self.len]);
+        self.reset();
+        command.map(Some)
+    }
+
+    /// Drop the command being reassembled
+    pub fn reset(&mut self) {
+        self.len = 0;
+        self.expected_len = 0;
+    }
+}
@@ -0,0 +1,154 @@
+//! Render frames as PNG images and animated GIFs
+//!
+//! Each led is drawn as a square of `scale` x `scale` pixels, with its hue
+//! byte decoded to the RGB color displayed by the device.
+//!
+//! # Example
+//!
+//! ```
+//!    use grove_matrix_led_my9221_rs::{render, Colors, Frame, Millis};
+//!
+//!    let mut frame = Frame::new();
+//!    frame.set_pixel(0, 0, Colors::Red);
+//!
+//!    let mut png = Vec::new();
+//!    render::write_png(&mut png, &frame, 16)?;
+//!
+//!    let mut gif = Vec::new();
+//!    let blue = Frame::filled(Colors::Blue);
+//!    render::write_gif(&mut gif, [(&frame, Millis::new(500)), (&blue, Millis::new(500))], 16)?;
+//!    # Ok::<(), render::RenderError>(())
+//! ```
+
+use std::io::Write;
+
+use crate::{
+    color::{Hue, Rgb},
+    Frame, Millis,
+};
+
+/// The errors that can occur when rendering frames
+#[derive(Debug)]
+pub enum RenderError {
+    /// The scale is 0 or the image would be too large
+    InvalidArgument,
+    /// An error reported by the PNG encoder
+    Png(png::EncodingError),
+    /// An error reported by the GIF encoder
+    Gif(gif::EncodingError),
+}
+
+impl std::fmt::Display for RenderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            RenderError::InvalidArgument => write!(f, "Invalid argument"),
+            RenderError::Png(e) => write!(f, "PNG error: {}", e),
+            RenderError::Gif(e) => write!(f, "GIF error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for RenderError {}
+
+impl From<png::EncodingError> for RenderError {
+    fn from(e: png::EncodingError) -> Self {
+        RenderError::Png(e)
+    }
+}
+
+impl From<gif::EncodingError> for RenderError {
+    fn from(e: gif::EncodingError) -> Self {
+        RenderError::Gif(e)
+    }
+}
+
+/// Palette mapping each hue byte to the RGB color displayed by the device
+fn palette() -> Vec<u8> {
+    (0..=0xff)
+        .flat_map(|hue| {
+            let Rgb { r, g, b } = Hue(hue).into();
+            [r, g, b]
+        })
+        .collect()
+}
+
+/// Size in pixels of a rendered frame
+fn size(scale: u16) -> Result<u16, RenderError> {
+    match scale.checked_mul(8) {
+        Some(size) if size > 0 => Ok(size),
+        _ => Err(RenderError::InvalidArgument),
+    }
+}
+
+/// Hue bytes of a rendered frame, one per pixel, row by row
+fn pixels(frame: &Frame, scale: u16) -> Vec<u8> {
+    let scale = scale as usize;
+    frame
+        .data
+        .chunks(8)
+        .flat_map(|row| {
+            let line: Vec<u8> = row
+                .iter()
+                .flat_map(|&hue| std::iter::repeat_n(hue, scale))
+                .collect();
+            std::iter::repeat_n(line, scale).flatten()
+        })
+        .collect()
+}
+
+/// Write a frame as a PNG image
+///
+/// # Arguments
+///
+/// * `writer` - Where to write the image
+/// * `frame` - The frame to render
+/// * `scale` - The size in pixels of each led
+///
+pub fn write_png<W: Write>(writer: W, frame: &Frame, scale: u16) -> Result<(), RenderError> {
+    let size = size(scale)? as u32;
+
+    let mut encoder = png::Encoder::new(writer, size, size);
+    encoder.set_color(png::ColorType::Indexed);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_palette(palette());
+
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&pixels(frame, scale))?;
+    writer.finish()?;
+    Ok(())
+}
+
+/// Write a sequence of frames as an animated GIF, looping forever
+///
+/// GIF delays are expressed in hundredths of a second: durations are rounded
+/// down to a multiple of 10 ms, and shorter than 10 ms ones are displayed for
+/// 10 ms, as a delay of 0 is played at a speed chosen by the viewer.
+///
+/// # Arguments
+///
+/// * `writer` - Where to write the image
+/// * `frames` - The frames to render with their duration
+/// * `scale` - The size in pixels of each led
+///
+pub fn write_gif<'a, W, I>(writer: W, frames: I, scale: u16) -> Result<(), RenderError>
+where
+    W: Write,
+    I: IntoIterator<Item = (&'a Frame, Millis)>,
+{
+    let size = size(scale)?;
+
+    let mut encoder = gif::Encoder::new(writer, size, size, &palette())?;
+    encoder.set_repeat(gif::Repeat::Infinite)?;
+
+    for (frame, duration) in frames {
+        let image = gif::Frame {
+            width: size,
+            height: size,
+            delay: (duration.as_ms() / 10).max(1),
+            buffer: pixels(frame, scale).into(),
+            ..gif::Frame::default()
+        };
+        encoder.write_frame(&image)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,374 @@
+//! In-memory simulator of the device firmware
+//!
+//! [`Simulator`] implements the embedded-hal [`I2c`] trait, decodes every
+//! command sent by the driver and keeps track of the state a real device
+//! would expose, so applications can be tested on a host without hardware.
+//!
+//! # Example
+//!
+//! ```
+//!    use grove_matrix_led_my9221_rs::{
+//!        simulator::{Content, Simulator},
+//!        DisplayRotate, Emojis, My9221LedMatrix, Playback,
+//!    };
+//!
+//!    let mut simulator = Simulator::default();
+//!    let led_matrix = My9221LedMatrix::default();
+//!
+//!    led_matrix.set_led_matrix_rotate(&mut simulator, DisplayRotate::Deg90)?;
+//!    led_matrix.display_emoji(&mut simulator, Emojis::Heart, Playback::Forever)?;
+//!
+//!    assert_eq!(simulator.rotation(), DisplayRotate::Deg90);
+//!    assert_eq!(
+//!        simulator.content(),
+//!        &Content::Emoji {
+//!            emoji: Emojis::Heart,
+//!            playback: Playback::Forever,
+//!        }
+//!    );
+//!    # Ok::<(), grove_matrix_led_my9221_rs::My9221LedMatrixError<embedded_hal::i2c::ErrorKind>>(())
+//! ```
+
+use embedded_hal::i2c::{ErrorKind, ErrorType, I2c, NoAcknowledgeSource, Operation};
+
+use crate::{
+    protocol::{Command, Decoder},
+    DisplayRotate, Emojis, Frame, Playback, DEFAULT_ADDRESS,
+};
+
+/// Number of frames the device can hold in its buffer and in flash
+const MAX_FRAMES: usize = 5;
+
+/// Device ID answered by the firmware, the USB vendor and product IDs of the
+/// module in little endian
+const DEVICE_ID: [u8; 4] = [0x86, 0x28, 0x05, 0x80];
+
+/// What the device is currently displaying
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Content {
+    /// Nothing is displayed
+    Off,
+    /// A bar
+    Bar {
+        bar: u8,
+        playback: Playback,
+        color: u8,
+    },
+    /// A built-in emoji
+    Emoji { emoji: Emojis, playback: Playback },
+    /// A number
+    Number {
+        number: u16,
+        playback: Playback,
+        color: u8,
+    },
+    /// A string
+    String {
+        string: String,
+        playback: Playback,
+        color: u8,
+    },
+    /// The user-defined frames held in the frame buffer
+    Frames { playback: Playback },
+    /// Frames stored in flash, `from_idx` and `to_idx` start at 1
+    Flash {
+        playback: Playback,
+        from_idx: u8,
+        to_idx: u8,
+    },
+    /// A color bar
+    ColorBar { bar: u8, playback: Playback },
+    /// The built-in wave animation
+    ColorWave { wave: u8, playback: Playback },
+    /// The built-in clockwise animation
+    ColorClockwise {
+        clockwise: bool,
+        big: bool,
+        playback: Playback,
+    },
+    /// A built-in animation, between two indexes of the built-in images
+    ColorAnimation {
+        from_idx: u8,
+        to_idx: u8,
+        playback: Playback,
+    },
+    /// A color block in RGB format (0x00RRGGBB)
+    ColorBlock { rgb: u32, playback: Playback },
+}
+
+/// A simulated grove matrix LED
+pub struct Simulator {
+    address: u8,
+    content: Content,
+    frames: Vec<Frame>,
+    flash: Vec<Frame>,
+    rotation: DisplayRotate,
+    offset: (u8, u8),
+    auto_sleep: bool,
+    led_flash: bool,
+    test_mode: bool,
+    version: [u8; 4],
+    uid: u8,
+    decoder: Decoder,
+    response: Vec<u8>,
+}
+
+impl Default for Simulator {
+    /// Create a simulated device answering at the default I2C address
+    fn default() -> Self {
+        Self::new(DEFAULT_ADDRESS)
+    }
+}
+
+impl Simulator {
+    /// Create a simulated device
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - The I2C address the device answers at
+    ///
+    pub fn new(address: u8) -> Self {
+        Self {
+            address,
+            content: Content::Off,
+            frames: Vec::new(),
+            flash: Vec::new(),
+            rotation: DisplayRotate::Deg0,
+            offset: (0, 0),
+            auto_sleep: false,
+            led_flash: false,
+            test_mode: false,
+            version: [0; 4],
+            uid: 0,
+            decoder: Decoder::new(),
+            response: Vec::new(),
+        }
+    }
+
+    /// Set the version answered to `test_get_version`
+    pub fn with_version(mut self, version: u32) -> Self {
+        self.version = version.to_be_bytes();
+        self
+    }
+
+    /// Set the UID answered to `get_device_uid`
+    pub fn with_uid(mut self, uid: u8) -> Self {
+        self.uid = uid;
+        self
+    }
+
+    /// The I2C address the device currently answers at
+    pub fn address(&self) -> u8 {
+        self.address
+    }
+
+    /// What the device is currently displaying
+    pub fn content(&self) -> &Content {
+        &self.content
+    }
+
+    /// The frames held in the frame buffer
+    ///
+    /// A frame which was never uploaded is black.
+    pub fn frames(&self) -> &[Frame] {
+        &self.frames
+    }
+
+    /// The frames stored in flash
+    pub fn flash(&self) -> &[Frame] {
+        &self.flash
+    }
+
+    /// The display orientation
+    pub fn rotation(&self) -> DisplayRotate {
+        self.rotation
+    }
+
+    /// The display offset (x, y)
+    pub fn offset(&self) -> (u8, u8) {
+        self.offset
+    }
+
+    /// Whether auto sleep mode is enabled
+    pub fn auto_sleep(&self) -> bool {
+        self.auto_sleep
+    }
+
+    /// Whether the indicator LED flash mode is on
+    pub fn led_flash(&self) -> bool {
+        self.led_flash
+    }
+
+    /// Whether TX RX pin test mode is enabled
+    pub fn test_mode(&self) -> bool {
+        self.test_mode
+    }
+
+    /// Handle a packet written to the device
+    fn receive(&mut self, packet: &[u8]) {
+        // Malformed commands are ignored by the firmware
+        if let Ok(Some(command)) = self.decoder.feed(packet) {
+            self.execute(command);
+        }
+    }
+
+    /// Execute a complete command
+    fn execute(&mut self, command: Command) {
+        match command {
+            Command::GetDeviceId => self.response = DEVICE_ID.to_vec(),
+            Command::DisplayBar {
+                bar,
+                playback,
+                color,
+            } => {
+                self.content = Content::Bar {
+                    bar,
+                    playback,
+                    color,
+                }
+            }
+            Command::DisplayEmoji { emoji, playback } => {
+                self.content = Content::Emoji { emoji, playback }
+            }
+            Command::DisplayNumber {
+                number,
+                playback,
+                color,
+            } => {
+                self.content = Content::Number {
+                    number,
+                    playback,
+                    color,
+                }
+            }
+            Command::DisplayString {
+                text,
+                playback,
+                color,
+            } => {
+                self.content = Content::String {
+                    string: text.to_string(),
+                    playback,
+                    color,
+                }
+            }
+            Command::DisplayCustom {
+                frame,
+                index,
+                frames_number,
+                playback,
+            } => {
+                let count = (frames_number as usize).min(MAX_FRAMES);
+                let index = index as usize;
+                if index >= count {
+                    return;
+                }
+                self.frames.resize(count, Frame::new());
+                self.frames[index] = frame;
+                // Frames are sent from the last one, the first one starts
+                // the display
+                if index == 0 {
+                    self.content = Content::Frames { playback };
+                }
+            }
+            Command::DisplayOff => self.content = Content::Off,
+            Command::DisplayFlash {
+                playback,
+                from_idx,
+                to_idx,
+            } => {
+                self.content = Content::Flash {
+                    playback,
+                    from_idx,
+                    to_idx,
+                }
+            }
+            Command::DisplayColorBar { bar, playback } => {
+                self.content = Content::ColorBar { bar, playback }
+            }
+            Command::DisplayColorWave { wave, playback } => {
+                self.content = Content::ColorWave { wave, playback }
+            }
+            Command::DisplayColorClockwise {
+                clockwise,
+                big,
+                playback,
+            } => {
+                self.content = Content::ColorClockwise {
+                    clockwise,
+                    big,
+                    playback,
+                }
+            }
+            Command::DisplayColorAnimation {
+                from_idx,
+                to_idx,
+                playback,
+            } => {
+                self.content = Content::ColorAnimation {
+                    from_idx,
+                    to_idx,
+                    playback,
+                }
+            }
+            Command::DisplayColorBlock { rgb, playback } => {
+                self.content = Content::ColorBlock { rgb, playback }
+            }
+            Command::StoreFlash => self.flash = self.frames.clone(),
+            Command::DeleteFlash => self.flash.clear(),
+            Command::LedFlashOn => self.led_flash = true,
+            Command::LedFlashOff => self.led_flash = false,
+            Command::AutoSleepOn => self.auto_sleep = true,
+            Command::AutoSleepOff => self.auto_sleep = false,
+            Command::Rotate(rotation) => self.rotation = rotation,
+            Command::Offset(x, y) => self.offset = (x, y),
+            Command::SetAddress(address) => self.address = address,
+            Command::ResetAddress => self.address = DEFAULT_ADDRESS,
+            Command::TestModeOn => self.test_mode = true,
+            Command::TestModeOff => self.test_mode = false,
+            Command::GetVersion => self.response = self.version.to_vec(),
+            Command::GetDeviceUid => self.response = vec![self.uid],
+        }
+    }
+
+    /// Fill a read buffer with the answer to the last query
+    fn respond(&self, buf: &mut [u8]) {
+        for (i, byte) in buf.iter_mut().enumerate() {
+            *byte = self.response.get(i).copied().unwrap_or(0);
+        }
+    }
+}
+
+impl ErrorType for Simulator {
+    type Error = ErrorKind;
+}
+
+impl I2c for Simulator {
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        if address != self.address {
+            return Err(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address));
+        }
+        for operation in operations {
+            match operation {
+                Operation::Write(packet) => self.receive(packet),
+                Operation::Read(buf) => self.respond(buf),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "async")]
+impl embedded_hal_async::i2c::I2c for Simulator {
+    async fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        I2c::transaction(self, address, operations)
+    }
+}
@@ -0,0 +1,128 @@
+//! Preview frames in a terminal supporting 24-bit ANSI colors
+//!
+//! Each character cell displays two leds on top of each other, using the
+//! upper half block character with the top led as foreground color and the
+//! bottom led as background color.
+//!
+//! # Example
+//!
+//! ```no_run
+//!    use grove_matrix_led_my9221_rs::{
+//!        terminal::TerminalPlayer, Colors, DisplayRotate, Frame, Millis,
+//!    };
+//!
+//!    let frames = [Frame::filled(Colors::Red), Frame::filled(Colors::Blue)];
+//!
+//!    let player = TerminalPlayer::new()
+//!        .with_rotation(DisplayRotate::Deg90)
+//!        .with_offset((1, 0));
+//!    player.play(&mut std::io::stdout(), &frames, Millis::new(500))?;
+//!    # Ok::<(), std::io::Error>(())
+//! ```
+
+use std::io::{self, Write};
+use std::thread;
+
+use crate::{
+    color::{Hue, Rgb},
+    DisplayRotate, Frame, Millis,
+};
+
+/// Number of terminal lines used to display a frame
+const LINES: usize = 4;
+
+/// Write a frame to a terminal
+///
+/// # Arguments
+///
+/// * `writer` - The terminal to write to
+/// * `frame` - The frame to display
+///
+pub fn write_frame<W: Write>(writer: &mut W, frame: &Frame) -> io::Result<()> {
+    for rows in frame.data.chunks(16) {
+        let (upper, lower) = rows.split_at(8);
+        for (&top, &bottom) in upper.iter().zip(lower) {
+            let front = Rgb::from(Hue(top));
+            let back = Rgb::from(Hue(bottom));
+            write!(
+                writer,
+                "\x1b[38;2;{};{};{}m\x1b[48;2;{};{};{}m\u{2580}",
+                front.r, front.g, front.b, back.r, back.g, back.b
+            )?;
+        }
+        writeln!(writer, "\x1b[0m")?;
+    }
+    writer.flush()
+}
+
+/// Play frames in a terminal the way the device displays them
+pub struct TerminalPlayer {
+    rotation: DisplayRotate,
+    offset: (u8, u8),
+}
+
+impl Default for TerminalPlayer {
+    /// Create a player without rotation nor offset
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TerminalPlayer {
+    /// Create a player without rotation nor offset
+    pub fn new() -> Self {
+        Self {
+            rotation: DisplayRotate::Deg0,
+            offset: (0, 0),
+        }
+    }
+
+    /// Rotate the frames, as set with
+    /// [`set_led_matrix_rotate`](crate::My9221LedMatrix::set_led_matrix_rotate)
+    pub fn with_rotation(mut self, rotation: DisplayRotate) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Offset the frames, as set with
+    /// [`set_led_matrix_offset`](crate::My9221LedMatrix::set_led_matrix_offset)
+    ///
+    /// The frames are moved `x` leds to the right and `y` leds down, leds
+    /// moved out of the display are lost.
+    pub fn with_offset(mut self, offset: (u8, u8)) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Apply the rotation then the offset to a frame
+    pub fn transform(&self, frame: &Frame) -> Frame {
+        frame
+            .rotated(self.rotation)
+            .shifted(self.offset.0 as i32, self.offset.1 as i32)
+    }
+
+    /// Play frames once, redrawing them in place
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - The terminal to write to
+    /// * `frames` - The frames to display
+    /// * `frame_time` - How long to display each frame
+    ///
+    pub fn play<W: Write>(
+        &self,
+        writer: &mut W,
+        frames: &[Frame],
+        frame_time: Millis,
+    ) -> io::Result<()> {
+        for (i, frame) in frames.iter().enumerate() {
+            if i > 0 {
+                // Move the cursor back to the top of the previous frame
+                write!(writer, "\x1b[{}A", LINES)?;
+            }
+            write_frame(writer, &self.transform(frame))?;
+            thread::sleep(frame_time.into());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,313 @@
+//! Host-side text rendering
+//!
+//! Unlike [`display_string`](crate::My9221LedMatrix::display_string), which
+//! uses the font of the firmware, text is rendered to [`Frame`]s with a
+//! bundled [`Font`], so it can be of any length, with a color per character,
+//! and scrolled in any direction, one column or row per frame. The frames
+//! can be sent to the device with
+//! [`stream_frames`](crate::My9221LedMatrix::stream_frames).
+//!
+//! The bundled fonts only cover printable ASCII, other characters are
+//! displayed as a hollow box, so accented text is better transliterated
+//! first, with [`transliterate`](crate::protocol::transliterate).
+//!
+//! # Example
+//!
+//! ```
+//!    use grove_matrix_led_my9221_rs::{
+//!        font::FONT_8X8_PROPORTIONAL,
+//!        protocol::transliterate,
+//!        text::{Scroll, TextRenderer},
+//!        Colors, Frame, Hue,
+//!    };
+//!
+//!    let renderer = TextRenderer::new(&FONT_8X8_PROPORTIONAL).with_scroll(Scroll::Left);
+//!
+//!    // One color for the whole text
+//!    let frames = renderer.render_str("Hi!", Colors::Red);
+//!    assert_eq!(frames.len(), 25);
+//!
+//!    // One color per character
+//!    let text = "Hello, world".chars().map(|c| {
+//!        let color = if c.is_ascii_uppercase() { Colors::Red } else { Colors::Blue };
+//!        (c, Hue::from(color))
+//!    });
+//!    let frames: Vec<Frame> = renderer.render(text).collect();
+//!    assert_eq!(frames.len(), 82);
+//!
+//!    // Accented characters transliterated to ASCII
+//!    let text = "Héllo".chars().flat_map(|c| transliterate(c).chars());
+//!    let frames = renderer.render(text.map(|c| (c, Hue::from(Colors::Red))));
+//!    assert_eq!(frames.len(), renderer.render_str("Hello", Colors::Red).len());
+//! ```
+
+use crate::{
+    color::Hue,
+    font::{self, Font},
+    Frame,
+};
+
+/// How the text moves on the display
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scroll {
+    /// The text enters from the right and moves to the left
+    Left,
+    /// The text enters from the left and moves to the right, the last
+    /// character first
+    Right,
+    /// The characters are centered and enter from the bottom one after the
+    /// other, moving up
+    Up,
+    /// The characters are centered and displayed one after the other, one
+    /// frame each
+    Static,
+}
+
+/// Renderer of text to frames
+#[derive(Debug, Clone, Copy)]
+pub struct TextRenderer<'a> {
+    font: &'a Font,
+    scroll: Scroll,
+    background: Hue,
+}
+
+impl<'a> TextRenderer<'a> {
+    /// Create a renderer scrolling the text to the left on a black
+    /// background
+    pub fn new(font: &'a Font) -> Self {
+        Self {
+            font,
+            scroll: Scroll::Left,
+            background: Hue::BLACK,
+        }
+    }
+
+    /// Set how the text moves on the display
+    pub fn with_scroll(mut self, scroll: Scroll) -> Self {
+        self.scroll = scroll;
+        self
+    }
+
+    /// Set the color of the leds which are not part of a character
+    pub fn with_background<C>(mut self, background: C) -> Self
+    where
+        C: Into<Hue>,
+    {
+        self.background = background.into();
+        self
+    }
+
+    /// Render characters, each with its own color
+    ///
+    /// The text is walked once to count the frames, then the frames follow
+    /// a cursor into it, which is cloned to look at the characters
+    /// displayed, so it must be cheap to clone. A right scroll walks it
+    /// from the back.
+    pub fn render<I>(&self, text: I) -> TextFrames<'a, I::IntoIter>
+    where
+        I: IntoIterator<Item = (char, Hue)>,
+        I::IntoIter: DoubleEndedIterator + Clone,
+    {
+        let text = text.into_iter();
+        let len = match self.scroll {
+            Scroll::Left | Scroll::Right => {
+                let width: usize = text
+                    .clone()
+                    .map(|(c, _)| self.font.columns(c).1 as usize)
+                    .sum();
+                width + Frame::WIDTH as usize
+            }
+            Scroll::Up => (text.clone().count() + 1) * Frame::HEIGHT as usize,
+            Scroll::Static => text.clone().count(),
+        };
+        let offset = match self.scroll {
+            // The cursor starts after the last column
+            Scroll::Right => len as isize - Frame::WIDTH as isize,
+            _ => 0,
+        };
+        TextFrames {
+            renderer: *self,
+            cursor: text,
+            offset,
+            index: 0,
+            len,
+        }
+    }
+
+    /// Render a string in a single color
+    pub fn render_str<'t, C>(
+        &self,
+        text: &'t str,
+        color: C,
+    ) -> TextFrames<'a, impl DoubleEndedIterator<Item = (char, Hue)> + Clone + 't>
+    where
+        C: Into<Hue>,
+    {
+        let color = color.into();
+        self.render(text.chars().map(move |c| (c, color)))
+    }
+}
+
+/// Iterator over the frames of a rendered text
+///
+/// Scrolling frames start with the first column or row of the text at the
+/// edge of the display and end with a blank frame, so they can be looped.
+pub struct TextFrames<'a, I> {
+    renderer: TextRenderer<'a>,
+    /// The characters which may still be displayed, the ones scrolled out
+    /// being consumed from the front, or from the back for a right scroll
+    cursor: I,
+    /// The first column of the cursor, its last column plus one for a right
+    /// scroll, or the index of its first character for the centered ones
+    offset: isize,
+    index: usize,
+    len: usize,
+}
+
+impl<I> TextFrames<'_, I>
+where
+    I: DoubleEndedIterator<Item = (char, Hue)> + Clone,
+{
+    /// Draw the text scrolled to the left, starting from its column
+    /// `first`, which may be negative
+    fn draw_from_front(&mut self, frame: &mut Frame, first: isize) {
+        let font = self.renderer.font;
+        let width = |c| font.columns(c).1 as isize;
+        while let Some((c, _)) = self.cursor.clone().next() {
+            if self.offset + width(c) > first {
+                break;
+            }
+            self.offset += width(c);
+            self.cursor.next();
+        }
+
+        let mut offset = self.offset;
+        for (c, hue) in self.cursor.clone() {
+            if offset >= first + Frame::WIDTH as isize {
+                break;
+            }
+            self.draw_columns(frame, c, hue, offset - first);
+            offset += width(c);
+        }
+    }
+
+    /// Draw the text scrolled to the right, starting from its column
+    /// `first`, which may be negative
+    fn draw_from_back(&mut self, frame: &mut Frame, first: isize) {
+        let font = self.renderer.font;
+        let width = |c| font.columns(c).1 as isize;
+        while let Some((c, _)) = self.cursor.clone().next_back() {
+            if self.offset - width(c) < first + Frame::WIDTH as isize {
+                break;
+            }
+            self.offset -= width(c);
+            self.cursor.next_back();
+        }
+
+        let mut offset = self.offset;
+        for (c, hue) in self.cursor.clone().rev() {
+            if offset <= first {
+                break;
+            }
+            offset -= width(c);
+            self.draw_columns(frame, c, hue, offset - first);
+        }
+    }
+
+    /// Draw the columns of a character starting from the column `x` of the
+    /// frame, which may be outside of it
+    fn draw_columns(&self, frame: &mut Frame, c: char, hue: Hue, x: isize) {
+        let font = self.renderer.font;
+        let glyph = font.glyph(c);
+        let (glyph_first, width) = font.columns(c);
+        for column in 0..width {
+            let x = x + column as isize;
+            let glyph_x = glyph_first + column;
+            if !(0..Frame::WIDTH as isize).contains(&x) || glyph_x >= 8 {
+                continue;
+            }
+            for (y, row) in glyph.iter().enumerate() {
+                if row & (1 << glyph_x) != 0 {
+                    frame.set_pixel(x as i32, y as i32, hue);
+                }
+            }
+        }
+    }
+
+    /// Move the cursor to the character at `index`, returning the
+    /// characters from there
+    fn seek(&mut self, index: usize) -> I {
+        while self.offset < index as isize && self.cursor.next().is_some() {
+            self.offset += 1;
+        }
+        self.cursor.clone()
+    }
+
+    /// Draw a character centered, moved `dy` rows down
+    fn draw_centered(&self, frame: &mut Frame, c: char, hue: Hue, dy: i32) {
+        let glyph = self.renderer.font.glyph(c);
+        let Some((first, last)) = font::lit_columns(&glyph) else {
+            return;
+        };
+        let dx = (Frame::WIDTH - (last - first + 1) as i32) / 2 - first as i32;
+        for (y, row) in glyph.iter().enumerate() {
+            for x in 0..8 {
+                if row & (1 << x) != 0 {
+                    frame.set_pixel(x + dx, y as i32 + dy, hue);
+                }
+            }
+        }
+    }
+}
+
+impl<I> Iterator for TextFrames<'_, I>
+where
+    I: DoubleEndedIterator<Item = (char, Hue)> + Clone,
+{
+    type Item = Frame;
+
+    fn next(&mut self) -> Option<Frame> {
+        if self.index >= self.len {
+            return None;
+        }
+        let index = self.index;
+        self.index += 1;
+
+        let mut frame = Frame::filled(self.renderer.background);
+        let (width, height) = (Frame::WIDTH as isize, Frame::HEIGHT as usize);
+        match self.renderer.scroll {
+            Scroll::Left => self.draw_from_front(&mut frame, index as isize - width + 1),
+            Scroll::Right => {
+                let first = (self.len as isize - width) - 1 - index as isize;
+                self.draw_from_back(&mut frame, first)
+            }
+            Scroll::Up => {
+                // Characters are stacked, the first row displayed being
+                // `first`, so at most two of them are visible
+                let first = index as i32 - height as i32 + 1;
+                let top = first.div_euclid(height as i32);
+                let chars = self.seek(top.max(0) as usize);
+                for (char_index, (c, hue)) in (top.max(0)..=top + 1).zip(chars) {
+                    let dy = char_index * height as i32 - first;
+                    self.draw_centered(&mut frame, c, hue, dy);
+                }
+            }
+            Scroll::Static => {
+                if let Some((c, hue)) = self.seek(index).next() {
+                    self.draw_centered(&mut frame, c, hue, 0);
+                }
+            }
+        }
+        Some(frame)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len - self.index;
+        (len, Some(len))
+    }
+}
+
+impl<I> ExactSizeIterator for TextFrames<'_, I> where
+    I: DoubleEndedIterator<Item = (char, Hue)> + Clone
+{
+}
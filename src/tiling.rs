@@ -0,0 +1,291 @@
+//! Several matrices displaying a single image
+//!
+//! Matrices given different addresses with
+//! [`set_address`](crate::My9221LedMatrix::set_address) can share a bus and
+//! be laid out as the tiles of a larger display, for example a 16x8 or 16x16
+//! sign. A [`TiledDisplay`] draws on the whole display and splits the image
+//! in one [`Frame`] per tile when it is sent.
+//!
+//! # Example
+//!
+//! ```
+//!    use embedded_hal_mock::eh1::{
+//!        delay::NoopDelay,
+//!        i2c::{Mock as I2cMock, Transaction as I2cTransaction},
+//!    };
+//!    use grove_matrix_led_my9221_rs::{
+//!        protocol::Command,
+//!        tiling::{Tile, TiledDisplay},
+//!        Colors, DisplayRotate, Frame, Hue, Playback,
+//!    };
+//!
+//!    // A 16x8 display, the right tile being mounted upside down
+//!    let mut display = TiledDisplay::new([
+//!        Tile::new(0x65, 0, 0),
+//!        Tile::new(0x66, 1, 0).with_rotation(DisplayRotate::Deg180),
+//!    ]);
+//!    assert_eq!((display.width(), display.height()), (16, 8));
+//!
+//!    display.set_pixel(8, 0, Colors::Red);
+//!    assert_eq!(display.get_pixel(8, 0), Some(Hue::from(Colors::Red)));
+//!
+//!    // The led is at the bottom right of the right tile once rotated
+//!    let mut right = Frame::new();
+//!    right.set_pixel(7, 7, Colors::Red);
+//!    let packets = |frame| {
+//!        let command = Command::DisplayCustom {
+//!            frame,
+//!            index: 0,
+//!            frames_number: 1,
+//!            playback: Playback::Forever,
+//!        };
+//!        command.packets()
+//!    };
+//!    // The packets of the tiles are interleaved
+//!    let expectations: Vec<_> = packets(Frame::new())
+//!        .zip(packets(right))
+//!        .flat_map(|(left, right)| {
+//!            [
+//!                I2cTransaction::write(0x65, left.as_bytes().to_vec()),
+//!                I2cTransaction::write(0x66, right.as_bytes().to_vec()),
+//!            ]
+//!        })
+//!        .collect();
+//!    let mut i2c = I2cMock::new(&expectations);
+//!
+//!    display.show(&mut i2c, &mut NoopDelay::new())?;
+//!
+//!    i2c.done();
+//!    # Ok::<(), grove_matrix_led_my9221_rs::My9221LedMatrixError<embedded_hal::i2c::ErrorKind>>(())
+//! ```
+
+use embedded_hal::{delay::DelayNs, i2c::I2c};
+
+use crate::{
+    color::Hue,
+    protocol::{Command, Packets},
+    DisplayRotate, Frame, My9221LedMatrix, My9221LedMatrixError, Playback,
+};
+
+/// A matrix and its place in a [`TiledDisplay`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tile {
+    /// The driver of the matrix
+    pub matrix: My9221LedMatrix,
+    /// The column of the tile, from the left
+    pub column: u8,
+    /// The row of the tile, from the top
+    pub row: u8,
+    /// The rotation applied to the part of the image displayed by the tile,
+    /// as [`set_led_matrix_rotate`](My9221LedMatrix::set_led_matrix_rotate)
+    /// would, to make up for the way the matrix is mounted
+    pub rotation: DisplayRotate,
+}
+
+impl Tile {
+    /// Create an unrotated tile
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - The I2C address of the matrix
+    /// * `column` - The column of the tile, from the left
+    /// * `row` - The row of the tile, from the top
+    ///
+    pub fn new(address: u8, column: u8, row: u8) -> Self {
+        Self {
+            matrix: My9221LedMatrix { address },
+            column,
+            row,
+            rotation: DisplayRotate::Deg0,
+        }
+    }
+
+    /// Set the rotation applied to the part of the image displayed by the
+    /// tile
+    pub fn with_rotation(mut self, rotation: DisplayRotate) -> Self {
+        self.rotation = rotation;
+        self
+    }
+}
+
+/// A display made of `N` tiles, with an image of their combined size
+///
+/// Leds of the image which are not covered by a tile are ignored.
+pub struct TiledDisplay<const N: usize> {
+    tiles: [Tile; N],
+    frames: [Frame; N],
+}
+
+impl<const N: usize> TiledDisplay<N> {
+    /// Create a display with a black image
+    pub fn new(tiles: [Tile; N]) -> Self {
+        Self {
+            tiles,
+            frames: [Frame::new(); N],
+        }
+    }
+
+    /// The tiles, in the order they were given
+    pub fn tiles(&self) -> &[Tile; N] {
+        &self.tiles
+    }
+
+    /// The part of the image displayed by each tile, before its rotation
+    pub fn frames(&self) -> &[Frame; N] {
+        &self.frames
+    }
+
+    /// The part of the image displayed by each tile, before its rotation
+    pub fn frames_mut(&mut self) -> &mut [Frame; N] {
+        &mut self.frames
+    }
+
+    /// The width of the image in leds
+    pub fn width(&self) -> i32 {
+        let columns = self.tiles.iter().map(|tile| tile.column as i32 + 1).max();
+        columns.unwrap_or(0) * Frame::WIDTH
+    }
+
+    /// The height of the image in leds
+    pub fn height(&self) -> i32 {
+        let rows = self.tiles.iter().map(|tile| tile.row as i32 + 1).max();
+        rows.unwrap_or(0) * Frame::HEIGHT
+    }
+
+    /// Set the color of the led at `(x, y)`, ignored outside of the tiles
+    pub fn set_pixel<C>(&mut self, x: i32, y: i32, color: C)
+    where
+        C: Into<Hue>,
+    {
+        let color = color.into();
+        for (i, x, y) in locate(&self.tiles, x, y) {
+            self.frames[i].set_pixel(x, y, color);
+        }
+    }
+
+    /// The color of the led at `(x, y)`, `None` outside of the tiles
+    pub fn get_pixel(&self, x: i32, y: i32) -> Option<Hue> {
+        let (i, x, y) = locate(&self.tiles, x, y).next()?;
+        self.frames[i].get_pixel(x, y)
+    }
+
+    /// Turn all the leds off
+    pub fn clear(&mut self) {
+        self.fill(Hue::BLACK);
+    }
+
+    /// Set all the leds to the same color
+    pub fn fill<C>(&mut self, color: C)
+    where
+        C: Into<Hue>,
+    {
+        let color = color.into();
+        for frame in &mut self.frames {
+            frame.fill(color);
+        }
+    }
+
+    /// Display the image, each tile showing its part as soon as it is sent
+    ///
+    /// # Arguments
+    ///
+    /// * `i2c` - The I2C peripheral to use
+    /// * `delay` - A delay provider
+    ///
+    pub fn show<I2C, D>(
+        &self,
+        i2c: &mut I2C,
+        delay: &mut D,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
+    where
+        I2C: I2c,
+        D: DelayNs,
+    {
+        self.display_frames(i2c, delay, &[self.frames], Playback::Forever, 1)
+    }
+
+    /// Display images, as [`My9221LedMatrix::display_frames`] does for a
+    /// single matrix
+    ///
+    /// The tiles start displaying the first image when they receive their
+    /// part of it, so it is sent to all the tiles in a row, once the other
+    /// images are sent, to start the animation of the tiles together.
+    ///
+    /// # Arguments
+    ///
+    /// * `i2c` - The I2C peripheral to use
+    /// * `delay` - A delay provider
+    /// * `images` - The images to display, as returned by
+    ///   [`frames`](Self::frames)
+    /// * `playback` - How long to display the images
+    /// * `frames_number` - The total number of images
+    ///
+    pub fn display_frames<I2C, D>(
+        &self,
+        i2c: &mut I2C,
+        delay: &mut D,
+        images: &[[Frame; N]],
+        playback: Playback,
+        frames_number: u8,
+    ) -> Result<(), My9221LedMatrixError<I2C::Error>>
+    where
+        I2C: I2c,
+        D: DelayNs,
+    {
+        let frames_number = frames_number.min(5);
+        if frames_number == 0 || images.len() < frames_number as usize {
+            return Err(My9221LedMatrixError::InvalidArgument);
+        }
+
+        for index in (0..frames_number).rev() {
+            let images = &images[index as usize];
+            let commands: [Command; N] = core::array::from_fn(|i| Command::DisplayCustom {
+                frame: images[i].rotated(self.tiles[i].rotation),
+                index,
+                frames_number,
+                playback,
+            });
+            // Every command is checked before any packet is written, as a
+            // tile refusing its frame would leave the others half updated
+            for command in &commands {
+                My9221LedMatrix::check(command)?;
+            }
+            let mut packets: [Packets; N] = core::array::from_fn(|i| commands[i].packets());
+
+            // Packets are written in rounds, one to each tile, so the tiles
+            // wait for the device together and receive the end of their
+            // frame, which starts the display, at about the same time
+            loop {
+                let mut written = false;
+                let mut delay_ms = 0;
+                for (tile, packets) in self.tiles.iter().zip(&mut packets) {
+                    if let Some(packet) = packets.next() {
+                        i2c.write(tile.matrix.address, packet.as_bytes())
+                            .map_err(My9221LedMatrixError::I2c)?;
+                        written = true;
+                        delay_ms = delay_ms.max(packet.delay_ms);
+                    }
+                }
+                if !written {
+                    break;
+                }
+                if delay_ms > 0 {
+                    delay.delay_ms(delay_ms);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The indexes of the tiles covering `(x, y)` and the position of the led in
+/// their frames
+fn locate(tiles: &[Tile], x: i32, y: i32) -> impl Iterator<Item = (usize, i32, i32)> + '_ {
+    let (column, row) = (x.div_euclid(Frame::WIDTH), y.div_euclid(Frame::HEIGHT));
+    let (tile_x, tile_y) = (x.rem_euclid(Frame::WIDTH), y.rem_euclid(Frame::HEIGHT));
+    tiles
+        .iter()
+        .enumerate()
+        .filter(move |(_, tile)| tile.column as i32 == column && tile.row as i32 == row)
+        .map(move |(i, _)| (i, tile_x, tile_y))
+}
@@ -0,0 +1,328 @@
+//! Bus traffic of each method of the blocking driver
+
+mod common;
+
+use common::{device_info, frames, query, readdressed, write};
+use embedded_hal::i2c::ErrorKind;
+use embedded_hal_mock::eh1::{
+    delay::NoopDelay,
+    i2c::{Mock as I2cMock, Transaction as I2cTransaction},
+};
+use grove_matrix_led_my9221_rs::{
+    animation::{Animation, Keyframe, Mode},
+    color::Rgb,
+    flash::FlashSlot,
+    info::{DeviceInfo, FirmwareVersion},
+    ColorAnimation, Colors, DisplayRotate, Emojis, Frame, Millis, My9221LedMatrix,
+    My9221LedMatrixError, Playback,
+};
+
+/// Call a method of the driver on a bus expecting `expectations`
+fn expect<T>(
+    expectations: &[I2cTransaction],
+    call: impl FnOnce(
+        &mut My9221LedMatrix,
+        &mut I2cMock,
+        &mut NoopDelay,
+    ) -> Result<T, My9221LedMatrixError<ErrorKind>>,
+) -> T {
+    let mut i2c = I2cMock::new(expectations);
+    let mut led_matrix = My9221LedMatrix::default();
+    let result = call(&mut led_matrix, &mut i2c, &mut NoopDelay::new()).unwrap();
+    i2c.done();
+    result
+}
+
+#[test]
+fn get_device_id() {
+    assert_eq!(
+        expect(&[query(0x00, &[0x86])], |led_matrix, i2c, _| led_matrix
+            .get_device_id(i2c)),
+        0x86
+    );
+}
+
+#[test]
+fn set_led_matrix_rotate() {
+    expect(&[write(&[0xb4, 0x01])], |led_matrix, i2c, _| {
+        led_matrix.set_led_matrix_rotate(i2c, DisplayRotate::Deg90)
+    });
+}
+
+#[test]
+fn stop_display() {
+    expect(&[write(&[0x06])], |led_matrix, i2c, _| {
+        led_matrix.stop_display(i2c)
+    });
+}
+
+#[test]
+fn set_led_matrix_offset() {
+    expect(&[write(&[0xb5, 1, 2])], |led_matrix, i2c, _| {
+        led_matrix.set_led_matrix_offset(i2c, (1, 2))
+    });
+}
+
+#[test]
+fn turn_on_led_flash() {
+    expect(&[write(&[0xb0])], |led_matrix, i2c, _| {
+        led_matrix.turn_on_led_flash(i2c)
+    });
+}
+
+#[test]
+fn turn_off_led_flash() {
+    expect(&[write(&[0xb1])], |led_matrix, i2c, _| {
+        led_matrix.turn_off_led_flash(i2c)
+    });
+}
+
+#[test]
+fn enable_auto_sleep() {
+    expect(&[write(&[0xb2])], |led_matrix, i2c, _| {
+        led_matrix.enable_auto_sleep(i2c)
+    });
+}
+
+#[test]
+fn disable_auto_sleep() {
+    expect(&[write(&[0xb3])], |led_matrix, i2c, _| {
+        led_matrix.disable_auto_sleep(i2c)
+    });
+}
+
+#[test]
+fn display_bar() {
+    expect(
+        &[write(&[0x01, 0x10, 0x00, 0x00, 0x01, 0x52])],
+        |led_matrix, i2c, _| led_matrix.display_bar(i2c, 16, Playback::Forever, Colors::Green),
+    );
+}
+
+#[test]
+fn display_emoji() {
+    expect(
+        &[write(&[0x02, 0x0a, 0xe8, 0x03, 0x00])],
+        |led_matrix, i2c, _| {
+            led_matrix.display_emoji(i2c, Emojis::Heart, Playback::Once(Millis::new(1_000)))
+        },
+    );
+}
+
+#[test]
+fn display_number() {
+    expect(
+        &[write(&[0x03, 0x2a, 0x00, 0x00, 0x00, 0x01, 0xaa])],
+        |led_matrix, i2c, _| led_matrix.display_number(i2c, 42, Playback::Forever, Colors::Blue),
+    );
+}
+
+#[test]
+fn display_string() {
+    expect(
+        &[write(&[0x04, 0x01, 0x00, 0x00, 0x02, 0x00, b'H', b'i'])],
+        |led_matrix, i2c, delay| {
+            led_matrix.display_string(i2c, delay, "Hi", Playback::Forever, Colors::Red)
+        },
+    );
+}
+
+#[test]
+fn display_string_transliterated() {
+    expect(
+        &[write(&[0x04, 0x01, 0x00, 0x00, 0x02, 0x00, b'H', b'e'])],
+        |led_matrix, i2c, delay| {
+            led_matrix.display_string_transliterated(
+                i2c,
+                delay,
+                "Hé",
+                Playback::Forever,
+                Colors::Red,
+            )
+        },
+    );
+}
+
+#[test]
+fn display_color_block() {
+    expect(
+        &[write(&[0x0d, 0xff, 0x00, 0x00, 0x00, 0x00, 0x00])],
+        |led_matrix, i2c, _| {
+            led_matrix.display_color_block(i2c, Rgb::new(0xff, 0x00, 0x00), Playback::Forever)
+        },
+    );
+}
+
+#[test]
+fn display_color_bar() {
+    expect(
+        &[write(&[0x09, 0x10, 0x00, 0x00, 0x00])],
+        |led_matrix, i2c, _| led_matrix.display_color_bar(i2c, 16, Playback::Forever),
+    );
+}
+
+#[test]
+fn display_color_wave() {
+    expect(
+        &[write(&[0x0a, 0x00, 0x00, 0x00, 0x00])],
+        |led_matrix, i2c, _| led_matrix.display_color_wave(i2c, 0, Playback::Forever),
+    );
+}
+
+#[test]
+fn display_color_clockwise() {
+    expect(
+        &[write(&[0x0b, 0x00, 0x01, 0x00, 0x00, 0x00])],
+        |led_matrix, i2c, _| {
+            led_matrix.display_color_clockwise(i2c, true, false, Playback::Forever)
+        },
+    );
+}
+
+#[test]
+fn display_color_animation() {
+    expect(
+        &[write(&[0x0c, 0xff, 0xff, 0x00, 0x00, 0x00])],
+        |led_matrix, i2c, _| {
+            led_matrix.display_color_animation(i2c, ColorAnimation::RainbowCycle, Playback::Forever)
+        },
+    );
+}
+
+#[test]
+fn display_frames() {
+    let red = Frame::filled(Colors::Red);
+
+    expect(&frames(&[red]), |led_matrix, i2c, delay| {
+        led_matrix.display_frames(i2c, delay, &[red], Playback::Forever, 1)
+    });
+}
+
+#[test]
+fn stream_frames() {
+    let (red, blue) = (Frame::filled(Colors::Red), Frame::filled(Colors::Blue));
+
+    expect(&frames(&[red, blue]), |led_matrix, i2c, delay| {
+        led_matrix.stream_frames(i2c, delay, [red, blue], Millis::new(100))
+    });
+}
+
+#[test]
+fn play_animation() {
+    let keyframes = [
+        Keyframe::new(Frame::filled(Colors::Red), Millis::new(100)),
+        Keyframe::new(Frame::filled(Colors::Blue), Millis::new(500)),
+    ];
+    let animation = Animation::new(&keyframes, Mode::Once);
+
+    expect(
+        &frames(&[keyframes[0].frame, keyframes[1].frame]),
+        |led_matrix, i2c, delay| led_matrix.play_animation(i2c, delay, &animation),
+    );
+}
+
+#[test]
+fn store_frames() {
+    expect(&[write(&[0xa0])], |led_matrix, i2c, delay| {
+        led_matrix.store_frames(i2c, delay)
+    });
+}
+
+#[test]
+fn delete_frames() {
+    expect(&[write(&[0xa1])], |led_matrix, i2c, delay| {
+        led_matrix.delete_frames(i2c, delay)
+    });
+}
+
+#[test]
+fn display_frames_from_flash() {
+    expect(
+        &[write(&[0x08, 0x00, 0x00, 0x00, 0x01, 0x02])],
+        |led_matrix, i2c, _| {
+            led_matrix.display_frames_from_flash(
+                i2c,
+                Playback::Forever,
+                FlashSlot::Slot1,
+                FlashSlot::Slot2,
+            )
+        },
+    );
+}
+
+#[test]
+fn enable_test_mode() {
+    expect(&[write(&[0xe0])], |led_matrix, i2c, _| {
+        led_matrix.enable_test_mode(i2c)
+    });
+}
+
+#[test]
+fn disable_test_mode() {
+    expect(&[write(&[0xe1])], |led_matrix, i2c, _| {
+        led_matrix.disable_test_mode(i2c)
+    });
+}
+
+#[test]
+fn test_get_version() {
+    assert_eq!(
+        expect(
+            &[query(0xe2, &[0x00, 0x01, 0x02, 0x03])],
+            |led_matrix, i2c, _| led_matrix.test_get_version(i2c)
+        ),
+        0x00010203
+    );
+}
+
+#[test]
+fn get_device_uid() {
+    assert_eq!(
+        expect(&[query(0xf1, &[0x2a])], |led_matrix, i2c, _| led_matrix
+            .get_device_uid(i2c)),
+        0x2a
+    );
+}
+
+#[test]
+fn get_device_info() {
+    assert_eq!(
+        expect(&device_info(), |led_matrix, i2c, _| led_matrix
+            .get_device_info(i2c)),
+        DeviceInfo {
+            device_id: 0x86,
+            firmware: FirmwareVersion::new(1, 2, 3),
+            uid: 0x2a,
+        }
+    );
+}
+
+#[test]
+fn set_address() {
+    assert_eq!(
+        expect(&[write(&[0xc0, 0x66])], |led_matrix, i2c, _| led_matrix
+            .set_address(i2c, 0x66)
+            .map(|()| led_matrix.address())),
+        0x66
+    );
+}
+
+#[test]
+fn reset_address() {
+    assert_eq!(
+        expect(&[write(&[0xc1])], |led_matrix, i2c, _| led_matrix
+            .reset_address(i2c)
+            .map(|()| led_matrix.address())),
+        0x65
+    );
+}
+
+#[test]
+fn readdress() {
+    assert_eq!(
+        expect(&readdressed(), |led_matrix, i2c, delay| led_matrix
+            .readdress(i2c, delay, 0x66)
+            .map(|()| led_matrix.address())),
+        0x66
+    );
+}
@@ -0,0 +1,45 @@
+//! Round trips between the hue bytes and the other color spaces
+
+use grove_matrix_led_my9221_rs::color::{Hsv, Hue, Rgb};
+
+#[test]
+fn white() {
+    assert_eq!(Rgb::from(Hue::WHITE), Rgb::new(0xff, 0xff, 0xff));
+    assert_eq!(Hue::from(Rgb::from(Hue::WHITE)), Hue::WHITE);
+    assert_eq!(Hsv::from(Hue::WHITE), Hsv::new(0, 0, 0xff));
+    assert_eq!(Hue::from(Hsv::from(Hue::WHITE)), Hue::WHITE);
+    assert!(!Hue(0xfe).is_wheel());
+}
+
+#[test]
+fn black() {
+    assert_eq!(Rgb::from(Hue::BLACK), Rgb::new(0x00, 0x00, 0x00));
+    assert_eq!(Hue::from(Rgb::from(Hue::BLACK)), Hue::BLACK);
+    assert_eq!(Hsv::from(Hue::BLACK), Hsv::new(0, 0, 0x00));
+    assert_eq!(Hue::from(Hsv::from(Hue::BLACK)), Hue::BLACK);
+    assert!(!Hue(0xff).is_wheel());
+}
+
+#[test]
+fn hsv_grey() {
+    for h in [0, 120, 359, 720] {
+        let grey = Hsv::new(h, 0, 0x80);
+        assert_eq!(Rgb::from(grey), Rgb::new(0x80, 0x80, 0x80));
+        assert_eq!(Hue::from(grey), Hue::WHITE);
+        // The hue of a grey is lost
+        assert_eq!(Hsv::from(Rgb::from(grey)), Hsv::new(0, 0, 0x80));
+        // Too dark to be lit
+        assert_eq!(Hue::from(Hsv::new(h, 0, 0x10)), Hue::BLACK);
+    }
+}
+
+#[test]
+fn wheel() {
+    for hue in (0..=0xfd).map(Hue) {
+        assert!(hue.is_wheel());
+        assert_eq!(Hue::from(Rgb::from(hue)), hue);
+        assert_eq!(Hue::from(Hsv::from(hue)), hue);
+        let hsv = Hsv::from(hue);
+        assert_eq!((hsv.s, hsv.v), (0xff, 0xff));
+    }
+}
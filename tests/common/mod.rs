@@ -0,0 +1,74 @@
+//! The bus traffic expected from the drivers, shared by the tests of the
+//! blocking, device and async drivers
+
+#![allow(dead_code)]
+
+use core::{
+    future::Future,
+    task::{Context, Poll, Waker},
+};
+
+use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource};
+use embedded_hal_mock::eh1::i2c::Transaction as I2cTransaction;
+use grove_matrix_led_my9221_rs::{protocol::Command, Frame, Playback};
+
+/// The default address of the device
+pub const ADDRESS: u8 = 0x65;
+
+/// A command written to the device
+pub fn write(bytes: &[u8]) -> I2cTransaction {
+    I2cTransaction::write(ADDRESS, bytes.to_vec())
+}
+
+/// A command written to the device and its answer read back
+pub fn query(command: u8, answer: &[u8]) -> I2cTransaction {
+    I2cTransaction::write_read(ADDRESS, vec![command], answer.to_vec())
+}
+
+/// Frames displayed one after the other, each uploaded alone
+pub fn frames(frames: &[Frame]) -> Vec<I2cTransaction> {
+    frames
+        .iter()
+        .flat_map(|&frame| {
+            Command::DisplayCustom {
+                frame,
+                index: 0,
+                frames_number: 1,
+                playback: Playback::Forever,
+            }
+            .packets()
+        })
+        .map(|packet| I2cTransaction::write(ADDRESS, packet.as_bytes().to_vec()))
+        .collect()
+}
+
+/// The device ID, firmware version 1.2.3 and UID 0x2a
+pub fn device_info() -> Vec<I2cTransaction> {
+    vec![
+        query(0x00, &[0x86]),
+        query(0xe2, &[0x00, 0x01, 0x02, 0x03]),
+        query(0xf1, &[0x2a]),
+    ]
+}
+
+/// The device moved to 0x66, where nothing answered before
+pub fn readdressed() -> Vec<I2cTransaction> {
+    vec![
+        I2cTransaction::read(0x66, vec![0x00])
+            .with_error(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address)),
+        write(&[0xc0, 0x66]),
+        I2cTransaction::write_read(0x66, vec![0x00], vec![0x86]),
+        I2cTransaction::write_read(0x66, vec![0xe2], vec![0x00, 0x01, 0x00, 0x00]),
+    ]
+}
+
+/// Run a future to completion, the mocks never leaving it pending
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = core::pin::pin!(future);
+    let mut cx = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
@@ -0,0 +1,131 @@
+//! Transparency, masks and ordering of the composited frames
+
+use grove_matrix_led_my9221_rs::{
+    compose::{Layer, Layers, Mask},
+    Frame, Hue,
+};
+
+/// A frame with the leds of a rectangle lit
+fn rect(x: i32, y: i32, width: u8, height: u8, hue: Hue) -> Frame {
+    let mut frame = Frame::new();
+    frame.fill_rect(x, y, width, height, hue);
+    frame
+}
+
+fn leds() -> impl Iterator<Item = (i32, i32)> {
+    (0..Frame::HEIGHT).flat_map(|y| (0..Frame::WIDTH).map(move |x| (x, y)))
+}
+
+#[test]
+fn transparent_leds_kept() {
+    let sprite = rect(2, 2, 2, 2, Hue(0x22));
+
+    // Black leds are transparent
+    let mut frame = Frame::filled(Hue(0x55));
+    frame.overlay(&sprite, Hue::BLACK);
+    for (x, y) in leds() {
+        let expected = if (2..4).contains(&x) && (2..4).contains(&y) {
+            Hue(0x22)
+        } else {
+            Hue(0x55)
+        };
+        assert_eq!(frame.get_pixel(x, y), Some(expected), "({x}, {y})");
+    }
+
+    // Any color may be transparent, the black leds are then drawn
+    let mut frame = Frame::filled(Hue(0x55));
+    frame.overlay(&sprite, Hue(0x22));
+    assert_eq!(frame, rect(2, 2, 2, 2, Hue(0x55)));
+
+    // Without a transparent color, a layer covers the ones below
+    let mut layers = Layers::<2>::new();
+    layers.push(Layer::new(Frame::filled(Hue(0x55)))).unwrap();
+    layers
+        .push(Layer::new(sprite).with_transparent(None))
+        .unwrap();
+    assert_eq!(layers.flatten(), sprite);
+}
+
+#[test]
+fn mask_clipping() {
+    let source = Frame::filled(Hue(0x22));
+
+    let mask = Mask::rect(1, 2, 3, 2);
+    let mut frame = Frame::new();
+    frame.overlay_masked(&source, mask);
+    assert_eq!(frame, rect(1, 2, 3, 2, Hue(0x22)));
+    for (x, y) in leds() {
+        assert_eq!(mask.get(x, y), frame.get_pixel(x, y) == Some(Hue(0x22)));
+    }
+
+    // Partly off the frame, only the leds inside are kept
+    let mask = Mask::rect(-2, 6, 4, 5);
+    assert_eq!(mask, Mask::rect(0, 6, 2, 2));
+    let mut frame = Frame::new();
+    frame.overlay_masked(&source, mask);
+    assert_eq!(frame, rect(0, 6, 2, 2, Hue(0x22)));
+
+    // Entirely off the frame, nothing is drawn
+    assert_eq!(Mask::rect(8, 0, 4, 4), Mask::NONE);
+    assert_eq!(Mask::rect(-4, -4, 4, 4), Mask::NONE);
+    assert_eq!(Mask::rect(i32::MAX, i32::MAX, 255, 255), Mask::NONE);
+    assert_eq!(Mask::rect(i32::MIN, 0, 255, 255), Mask::NONE);
+    assert_eq!(Mask::rect(-200, -200, 255, 255), Mask::ALL);
+    assert!(!mask.get(-1, 6));
+    assert!(!Mask::ALL.get(8, 0));
+
+    // Masks and transparency combine, a layer only drawing opaque leds in
+    // its mask
+    let mut layers = Layers::<2>::new();
+    layers.push(Layer::new(Frame::filled(Hue(0x55)))).unwrap();
+    layers
+        .push(Layer::new(rect(2, 2, 4, 4, Hue(0x22))).with_mask(Mask::rect(0, 0, 4, 8)))
+        .unwrap();
+    let mut expected = Frame::filled(Hue(0x55));
+    expected.fill_rect(2, 2, 2, 4, Hue(0x22));
+    assert_eq!(layers.flatten(), expected);
+}
+
+#[test]
+fn layer_ordering() {
+    let mut layers = Layers::<3>::new();
+    layers
+        .push(Layer::new(rect(0, 0, 4, 4, Hue(0x22))))
+        .unwrap();
+    layers
+        .push(Layer::new(rect(2, 2, 4, 4, Hue(0x33))))
+        .unwrap();
+    layers
+        .push(Layer::new(rect(3, 3, 1, 1, Hue(0x44))))
+        .unwrap();
+    assert_eq!(
+        layers.push(Layer::new(Frame::new())),
+        Err(Layer::new(Frame::new()))
+    );
+
+    // The later layers are drawn over the earlier ones
+    let frame = layers.flatten();
+    assert_eq!(frame.get_pixel(1, 1), Some(Hue(0x22)));
+    assert_eq!(frame.get_pixel(2, 2), Some(Hue(0x33)));
+    assert_eq!(frame.get_pixel(3, 3), Some(Hue(0x44)));
+    assert_eq!(frame.get_pixel(5, 5), Some(Hue(0x33)));
+    assert_eq!(frame.get_pixel(7, 7), Some(Hue::BLACK));
+
+    // Moved to the top, the bottom layer covers the others
+    layers.layers_mut().swap(0, 2);
+    let frame = layers.flatten();
+    assert_eq!(frame.get_pixel(2, 2), Some(Hue(0x22)));
+    assert_eq!(frame.get_pixel(3, 3), Some(Hue(0x22)));
+    assert_eq!(frame.get_pixel(5, 5), Some(Hue(0x33)));
+
+    // A hidden layer is not drawn
+    layers.layers_mut()[2].visible = false;
+    let frame = layers.flatten();
+    assert_eq!(frame.get_pixel(3, 3), Some(Hue(0x33)));
+
+    assert!(layers.pop().is_some());
+    assert_eq!(layers.len(), 2);
+    layers.clear();
+    assert!(layers.is_empty());
+    assert_eq!(layers.flatten(), Frame::new());
+}
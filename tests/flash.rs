@@ -0,0 +1,205 @@
+//! Bus traffic of the flash manager
+
+use embedded_hal::i2c::ErrorKind;
+use embedded_hal_mock::eh1::{
+    delay::NoopDelay,
+    i2c::{Mock as I2cMock, Transaction as I2cTransaction},
+};
+use grove_matrix_led_my9221_rs::{
+    flash::{FlashManager, FlashSlot},
+    protocol::Command,
+    Frame, My9221LedMatrix, My9221LedMatrixError, Playback,
+};
+
+fn write(command: Command) -> Vec<I2cTransaction> {
+    command
+        .packets()
+        .map(|packet| I2cTransaction::write(0x65, packet.as_bytes().to_vec()))
+        .collect()
+}
+
+/// The frames uploaded, the last one first, then stored
+fn store(frames: &[Frame]) -> Vec<I2cTransaction> {
+    (0..frames.len())
+        .rev()
+        .flat_map(|index| {
+            write(Command::DisplayCustom {
+                frame: frames[index],
+                index: index as u8,
+                frames_number: frames.len() as u8,
+                playback: Playback::Forever,
+            })
+        })
+        .chain(write(Command::StoreFlash))
+        .collect()
+}
+
+fn play(from_idx: u8, to_idx: u8) -> Vec<I2cTransaction> {
+    write(Command::DisplayFlash {
+        playback: Playback::Forever,
+        from_idx,
+        to_idx,
+    })
+}
+
+fn frame(i: u8) -> Frame {
+    Frame::filled(i)
+}
+
+/// Upload `a` to slots 1 and 2, `b` to slot 3 and `c` to slot 4
+fn upload_all(i2c: &mut I2cMock) -> FlashManager<'static> {
+    let mut flash = FlashManager::new(My9221LedMatrix::default());
+    let mut delay = NoopDelay::new();
+    for (name, frames) in [
+        ("a", &[frame(1), frame(2)][..]),
+        ("b", &[frame(3)]),
+        ("c", &[frame(4)]),
+    ] {
+        flash
+            .upload(i2c, &mut delay, name, frames, Playback::Forever)
+            .unwrap();
+    }
+    flash
+}
+
+/// The transactions of `upload_all`, the frames already stored being
+/// uploaded again before the new ones
+fn uploads() -> Vec<I2cTransaction> {
+    [
+        store(&[frame(1), frame(2)]),
+        play(1, 2),
+        store(&[frame(1), frame(2), frame(3)]),
+        play(3, 3),
+        store(&[frame(1), frame(2), frame(3), frame(4)]),
+        play(4, 4),
+    ]
+    .concat()
+}
+
+#[test]
+fn upload_keeps_stored_frames() {
+    let mut i2c = I2cMock::new(&uploads());
+
+    let flash = upload_all(&mut i2c);
+
+    assert_eq!(flash.animations().collect::<Vec<_>>(), ["a", "b", "c"]);
+    assert_eq!(
+        flash.animation("a"),
+        Some((FlashSlot::Slot1, FlashSlot::Slot2))
+    );
+    assert_eq!(flash.frame(FlashSlot::Slot4), Some(&frame(4)));
+    assert_eq!(flash.free_slots(), 1);
+    i2c.done();
+}
+
+#[test]
+fn remove_moves_following_animations() {
+    let mut i2c = I2cMock::new(
+        &[
+            uploads(),
+            store(&[frame(1), frame(2), frame(4)]),
+            write(Command::DisplayOff),
+            store(&[frame(4)]),
+            write(Command::DisplayOff),
+        ]
+        .concat(),
+    );
+    let mut flash = upload_all(&mut i2c);
+
+    flash.remove(&mut i2c, &mut NoopDelay::new(), "b").unwrap();
+    assert_eq!(
+        flash.animation("c"),
+        Some((FlashSlot::Slot3, FlashSlot::Slot3))
+    );
+    assert_eq!(flash.name(FlashSlot::Slot4), None);
+
+    flash.remove(&mut i2c, &mut NoopDelay::new(), "a").unwrap();
+    assert_eq!(flash.animations().collect::<Vec<_>>(), ["c"]);
+    assert_eq!(flash.frame(FlashSlot::Slot1), Some(&frame(4)));
+    assert_eq!(flash.free_slots(), 4);
+    i2c.done();
+}
+
+#[test]
+fn remove_last_animation_clears() {
+    let mut i2c =
+        I2cMock::new(&[store(&[frame(1)]), play(1, 1), write(Command::DeleteFlash)].concat());
+    let mut delay = NoopDelay::new();
+    let mut flash = FlashManager::new(My9221LedMatrix::default());
+    flash
+        .upload(&mut i2c, &mut delay, "a", &[frame(1)], Playback::Forever)
+        .unwrap();
+
+    flash.remove(&mut i2c, &mut delay, "a").unwrap();
+
+    assert_eq!(flash.animations().count(), 0);
+    assert_eq!(flash.free_slots(), 5);
+    i2c.done();
+}
+
+#[test]
+fn remove_unknown_animation() {
+    let mut i2c = I2cMock::new(&uploads());
+    let mut flash = upload_all(&mut i2c);
+
+    assert!(matches!(
+        flash.remove(&mut i2c, &mut NoopDelay::new(), "d"),
+        Err(My9221LedMatrixError::InvalidArgument)
+    ));
+    i2c.done();
+}
+
+#[test]
+fn failed_store_keeps_slots() {
+    let mut store = store(&[frame(1), frame(2), frame(4)]);
+    let last = store.pop().unwrap();
+    store.push(last.with_error(ErrorKind::Bus));
+    let mut i2c = I2cMock::new(&[uploads(), store].concat());
+    let mut flash = upload_all(&mut i2c);
+
+    assert!(matches!(
+        flash.remove(&mut i2c, &mut NoopDelay::new(), "b"),
+        Err(My9221LedMatrixError::I2c(ErrorKind::Bus))
+    ));
+    assert_eq!(flash.animations().collect::<Vec<_>>(), ["a", "b", "c"]);
+    i2c.done();
+}
+
+#[test]
+fn flash_full() {
+    let mut i2c = I2cMock::new(
+        &[
+            uploads(),
+            store(&[frame(1), frame(2), frame(3), frame(4), frame(5)]),
+            play(5, 5),
+        ]
+        .concat(),
+    );
+    let mut delay = NoopDelay::new();
+    let mut flash = upload_all(&mut i2c);
+
+    // Nothing is written when the frames don't fit
+    assert!(matches!(
+        flash.upload(
+            &mut i2c,
+            &mut delay,
+            "d",
+            &[frame(5), frame(6)],
+            Playback::Forever
+        ),
+        Err(My9221LedMatrixError::FlashFull)
+    ));
+    assert_eq!(flash.free_slots(), 1);
+
+    assert_eq!(
+        flash
+            .upload(&mut i2c, &mut delay, "d", &[frame(5)], Playback::Forever)
+            .unwrap(),
+        (FlashSlot::Slot5, FlashSlot::Slot5)
+    );
+    assert!(matches!(
+        flash.upload(&mut i2c, &mut delay, "e", &[frame(6)], Playback::Forever),
+        Err(My9221LedMatrixError::FlashFull)
+    ));
+    i2c.done();
+}
@@ -0,0 +1,221 @@
+//! Drawing and transformations of the frames
+
+use grove_matrix_led_my9221_rs::{DisplayRotate, Frame, Hue};
+
+/// A frame with a different color for each led
+fn pattern() -> Frame {
+    Frame {
+        data: core::array::from_fn(|i| i as u8),
+    }
+}
+
+/// The color of the led at `(x, y)` in the pattern
+fn led(x: i32, y: i32) -> Option<Hue> {
+    pattern().get_pixel(x, y)
+}
+
+fn leds() -> impl Iterator<Item = (i32, i32)> {
+    (0..Frame::HEIGHT).flat_map(|y| (0..Frame::WIDTH).map(move |x| (x, y)))
+}
+
+#[test]
+fn shifted_at_each_edge() {
+    let frame = pattern();
+
+    for (dx, dy) in [(1, 0), (-1, 0), (0, 1), (0, -1), (3, -2)] {
+        let shifted = frame.shifted(dx, dy);
+        for (x, y) in leds() {
+            let (from_x, from_y) = (x - dx, y - dy);
+            let expected = if (0..8).contains(&from_x) && (0..8).contains(&from_y) {
+                led(from_x, from_y)
+            } else {
+                // Uncovered at the opposite edge
+                Some(Hue::BLACK)
+            };
+            assert_eq!(
+                shifted.get_pixel(x, y),
+                expected,
+                "({dx}, {dy}) at ({x}, {y})"
+            );
+        }
+    }
+
+    // Every led leaves the frame
+    for (dx, dy) in [(8, 0), (-8, 0), (0, 8), (0, -8), (i32::MAX, i32::MIN)] {
+        assert_eq!(frame.shifted(dx, dy), Frame::new());
+    }
+}
+
+#[test]
+fn shifted_cyclic_at_each_edge() {
+    let frame = pattern();
+
+    assert_eq!(frame.shifted_cyclic(1, 0).get_pixel(0, 2), led(7, 2));
+    assert_eq!(frame.shifted_cyclic(-1, 0).get_pixel(7, 2), led(0, 2));
+    assert_eq!(frame.shifted_cyclic(0, 1).get_pixel(2, 0), led(2, 7));
+    assert_eq!(frame.shifted_cyclic(0, -1).get_pixel(2, 7), led(2, 0));
+    assert_eq!(frame.shifted_cyclic(8, -16), frame);
+    assert_eq!(frame.shifted_cyclic(-9, 9), frame.shifted_cyclic(-1, 1));
+    assert_eq!(
+        frame.shifted_cyclic(i32::MIN, i32::MAX),
+        frame.shifted_cyclic(0, 7)
+    );
+}
+
+#[test]
+fn transposed() {
+    let frame = pattern();
+    let transposed = frame.transposed();
+
+    for (x, y) in leds() {
+        assert_eq!(transposed.get_pixel(x, y), led(y, x));
+    }
+    assert_eq!(transposed.transposed(), frame);
+    // Transposing is flipping along the other diagonal of a rotated frame
+    assert_eq!(
+        transposed,
+        frame.rotated(DisplayRotate::Deg90).flipped_horizontal()
+    );
+}
+
+#[test]
+fn rotated() {
+    let frame = pattern();
+    let rotate90 = |frame: Frame| frame.rotated(DisplayRotate::Deg90);
+
+    assert_eq!(rotate90(rotate90(rotate90(rotate90(frame)))), frame);
+    assert_eq!(
+        rotate90(rotate90(frame)),
+        frame.rotated(DisplayRotate::Deg180)
+    );
+    assert_eq!(
+        rotate90(rotate90(rotate90(frame))),
+        frame.rotated(DisplayRotate::Deg270)
+    );
+    assert_eq!(frame.rotated(DisplayRotate::Deg0), frame);
+    // The corners move clockwise
+    let rotated = rotate90(frame);
+    assert_eq!(rotated.get_pixel(7, 0), led(0, 0));
+    assert_eq!(rotated.get_pixel(7, 7), led(7, 0));
+    assert_eq!(rotated.get_pixel(0, 7), led(7, 7));
+    assert_eq!(rotated.get_pixel(0, 0), led(0, 7));
+}
+
+#[test]
+fn shapes_clipped() {
+    // A shape partly outside of the frame is the part of the same shape
+    // drawn inside and moved out
+    let drawn = |draw: &dyn Fn(&mut Frame, i32, i32)| {
+        let mut clipped = Frame::new();
+        draw(&mut clipped, -2, 1);
+        let mut inside = Frame::new();
+        draw(&mut inside, 2, 4);
+        assert_eq!(clipped, inside.shifted(-4, -3));
+        assert_ne!(clipped, Frame::new());
+        clipped
+    };
+
+    drawn(&|frame, x, y| frame.draw_rect(x, y, 5, 3, Hue(0x22)));
+    drawn(&|frame, x, y| frame.fill_rect(x, y, 5, 3, Hue(0x22)));
+    drawn(&|frame, x, y| frame.draw_circle(x + 1, y, 2, Hue(0x22)));
+    let circle = drawn(&|frame, x, y| frame.fill_circle(x + 1, y, 2, Hue(0x22)));
+    assert_eq!(circle.get_pixel(0, 1), Some(Hue(0x22)));
+    assert_eq!(circle.get_pixel(1, 1), Some(Hue(0x22)));
+    assert_eq!(circle.get_pixel(2, 1), Some(Hue::BLACK));
+
+    // Shapes entirely outside of the frame draw nothing
+    let mut frame = Frame::new();
+    frame.draw_rect(-10, 3, 5, 5, Hue(0x22));
+    frame.fill_rect(8, 8, 5, 5, Hue(0x22));
+    frame.draw_circle(3, -20, 10, Hue(0x22));
+    frame.fill_circle(-20, -20, 10, Hue(0x22));
+    assert_eq!(frame, Frame::new());
+
+    // A circle larger than the frame only draws the leds of its outline
+    let mut frame = Frame::new();
+    frame.draw_circle(3, 3, 20, Hue(0x22));
+    assert_eq!(frame, Frame::new());
+    frame.fill_circle(3, 3, 20, Hue(0x22));
+    assert_eq!(frame, Frame::filled(Hue(0x22)));
+}
+
+#[test]
+fn flood_fill_touching_border() {
+    let mut frame = Frame::new();
+    frame.draw_rect(2, 2, 4, 4, Hue(0x22));
+
+    // The area around the rectangle touches the four borders
+    frame.flood_fill(7, 0, Hue(0x33));
+
+    for (x, y) in leds() {
+        let expected = match (x, y) {
+            (2..=5, 2..=5) if x == 2 || x == 5 || y == 2 || y == 5 => Hue(0x22),
+            (3..=4, 3..=4) => Hue::BLACK,
+            _ => Hue(0x33),
+        };
+        assert_eq!(frame.get_pixel(x, y), Some(expected), "({x}, {y})");
+    }
+
+    // A whole frame of the same color is filled from a corner
+    let mut frame = Frame::new();
+    frame.flood_fill(0, 7, Hue(0x33));
+    assert_eq!(frame, Frame::filled(Hue(0x33)));
+
+    // Outside of the frame nothing is filled
+    frame.flood_fill(8, 0, Hue(0x22));
+    frame.flood_fill(-1, 3, Hue(0x22));
+    assert_eq!(frame, Frame::filled(Hue(0x33)));
+}
+
+#[test]
+fn shapes_at_extreme_coordinates() {
+    let mut frame = Frame::new();
+
+    // Nothing overflows, and shapes far outside of the frame draw nothing
+    for (x, y) in [
+        (i32::MAX, i32::MAX),
+        (i32::MAX - 3, 2),
+        (2, i32::MAX - 3),
+        (i32::MIN, i32::MIN),
+        (i32::MIN, 2),
+    ] {
+        frame.draw_rect(x, y, 255, 255, Hue(0x22));
+        frame.fill_rect(x, y, 255, 255, Hue(0x22));
+        frame.draw_circle(x, y, 255, Hue(0x22));
+        frame.fill_circle(x, y, 255, Hue(0x22));
+        frame.draw_line(x, y, x, y, Hue(0x22));
+    }
+    assert_eq!(frame, Frame::new());
+
+    // A rectangle from far outside of the frame covers it
+    frame.fill_rect(-200, -200, 255, 255, Hue(0x22));
+    assert_eq!(frame, Frame::filled(Hue(0x22)));
+
+    // Lines spanning the whole range of coordinates are clipped, at the
+    // leds they would go through
+    let mut frame = Frame::new();
+    frame.draw_line(i32::MIN, 3, i32::MAX, 3, Hue(0x22));
+    frame.draw_line(5, i32::MAX, 5, i32::MIN, Hue(0x33));
+    for (x, y) in leds() {
+        let expected = match (x, y) {
+            (5, _) => Hue(0x33),
+            (_, 3) => Hue(0x22),
+            _ => Hue::BLACK,
+        };
+        assert_eq!(frame.get_pixel(x, y), Some(expected), "({x}, {y})");
+    }
+
+    // The diagonal through the origin, with its ends far apart
+    let mut frame = Frame::new();
+    frame.draw_line(i32::MIN + 1, i32::MIN + 1, i32::MAX, i32::MAX, Hue(0x22));
+    let mut diagonal = Frame::new();
+    diagonal.draw_line(0, 0, 7, 7, Hue(0x22));
+    assert_eq!(frame, diagonal);
+
+    // A line between far points crossing the frame
+    let mut frame = Frame::new();
+    frame.draw_line(-1_000_000_000, 0, 1_000_000_000, 2, Hue(0x22));
+    let mut expected = Frame::new();
+    expected.draw_line(0, 1, 7, 1, Hue(0x22));
+    assert_eq!(frame, expected);
+}
@@ -0,0 +1,136 @@
+//! Frames and tiled displays drawn with embedded-graphics
+
+use embedded_graphics::{
+    pixelcolor::Rgb888,
+    prelude::*,
+    primitives::{Line, PrimitiveStyle, Rectangle},
+};
+use grove_matrix_led_my9221_rs::{
+    tiling::{Tile, TiledDisplay},
+    Frame, Hue,
+};
+
+fn leds() -> impl Iterator<Item = (i32, i32)> {
+    (0..Frame::HEIGHT).flat_map(|y| (0..Frame::WIDTH).map(move |x| (x, y)))
+}
+
+#[test]
+fn colors_to_hues() {
+    let hue = |rgb: Rgb888| {
+        let mut frame = Frame::new();
+        Pixel(Point::new(3, 4), rgb).draw(&mut frame).unwrap();
+        frame.get_pixel(3, 4).unwrap()
+    };
+
+    assert_eq!(hue(Rgb888::RED), Hue(0x00));
+    assert_eq!(hue(Rgb888::GREEN), Hue(0x55));
+    assert_eq!(hue(Rgb888::BLUE), Hue(0xaa));
+    assert_eq!(hue(Rgb888::YELLOW), Hue(0x2b));
+    assert_eq!(hue(Rgb888::WHITE), Hue::WHITE);
+    assert_eq!(hue(Rgb888::BLACK), Hue::BLACK);
+    // Greys are white, unless too dark to be lit
+    assert_eq!(hue(Rgb888::new(0x80, 0x80, 0x80)), Hue::WHITE);
+    assert_eq!(hue(Rgb888::new(0x10, 0x10, 0x10)), Hue::BLACK);
+    // The same as the conversion of the colors
+    assert_eq!(
+        hue(Rgb888::new(0xff, 0x80, 0x00)),
+        Hue::from(Rgb888::new(0xff, 0x80, 0x00))
+    );
+}
+
+#[test]
+fn frame_primitives() {
+    let mut frame = Frame::new();
+    assert_eq!(frame.size(), Size::new(8, 8));
+
+    DrawTarget::clear(&mut frame, Rgb888::BLUE).unwrap();
+    assert_eq!(frame, Frame::filled(Hue(0xaa)));
+
+    Rectangle::new(Point::new(1, 2), Size::new(3, 2))
+        .into_styled(PrimitiveStyle::with_fill(Rgb888::RED))
+        .draw(&mut frame)
+        .unwrap();
+    Line::new(Point::new(0, 7), Point::new(7, 7))
+        .into_styled(PrimitiveStyle::with_stroke(Rgb888::GREEN, 1))
+        .draw(&mut frame)
+        .unwrap();
+
+    for (x, y) in leds() {
+        let expected = match (x, y) {
+            (1..=3, 2..=3) => Hue(0x00),
+            (_, 7) => Hue(0x55),
+            _ => Hue(0xaa),
+        };
+        assert_eq!(frame.get_pixel(x, y), Some(expected), "({x}, {y})");
+    }
+}
+
+#[test]
+fn frame_out_of_bounds_discarded() {
+    let mut frame = Frame::new();
+
+    // Only the leds of the frame are drawn
+    Rectangle::new(Point::new(-3, 6), Size::new(5, 5))
+        .into_styled(PrimitiveStyle::with_fill(Rgb888::RED))
+        .draw(&mut frame)
+        .unwrap();
+    for point in [
+        Point::new(8, 0),
+        Point::new(-1, 0),
+        Point::new(0, 8),
+        Point::new(i32::MIN, i32::MAX),
+    ] {
+        Pixel(point, Rgb888::BLUE).draw(&mut frame).unwrap();
+    }
+
+    let mut expected = Frame::new();
+    expected.fill_rect(0, 6, 2, 2, Hue(0x00));
+    assert_eq!(frame, expected);
+}
+
+#[test]
+fn tiled_display_primitives() {
+    // Two tiles side by side, and one below the second one
+    let mut display = TiledDisplay::new([
+        Tile::new(0x65, 0, 0),
+        Tile::new(0x66, 1, 0),
+        Tile::new(0x67, 1, 1),
+    ]);
+    assert_eq!(display.size(), Size::new(16, 16));
+
+    // A line across the first two tiles
+    Line::new(Point::new(4, 3), Point::new(11, 3))
+        .into_styled(PrimitiveStyle::with_stroke(Rgb888::RED, 1))
+        .draw(&mut display)
+        .unwrap();
+    // A rectangle across the last two tiles, and where no tile is
+    Rectangle::new(Point::new(6, 6), Size::new(4, 4))
+        .into_styled(PrimitiveStyle::with_fill(Rgb888::BLUE))
+        .draw(&mut display)
+        .unwrap();
+    // Outside of the display
+    Pixel(Point::new(16, 3), Rgb888::GREEN)
+        .draw(&mut display)
+        .unwrap();
+    Pixel(Point::new(-1, 3), Rgb888::GREEN)
+        .draw(&mut display)
+        .unwrap();
+
+    let [first, second, third] = *display.frames();
+    let mut expected = Frame::new();
+    expected.draw_line(4, 3, 7, 3, Hue(0x00));
+    expected.fill_rect(6, 6, 2, 2, Hue(0xaa));
+    assert_eq!(first, expected);
+    let mut expected = Frame::new();
+    expected.draw_line(0, 3, 3, 3, Hue(0x00));
+    expected.fill_rect(0, 6, 2, 2, Hue(0xaa));
+    assert_eq!(second, expected);
+    let mut expected = Frame::new();
+    expected.fill_rect(0, 0, 2, 2, Hue(0xaa));
+    assert_eq!(third, expected);
+    // The leds where no tile is are ignored
+    assert_eq!(display.get_pixel(6, 8), None);
+
+    DrawTarget::clear(&mut display, Rgb888::WHITE).unwrap();
+    assert_eq!(*display.frames(), [Frame::filled(Hue::WHITE); 3]);
+}
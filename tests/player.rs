@@ -0,0 +1,46 @@
+//! Checks of the jobs started on a player
+
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+use grove_matrix_led_my9221_rs::{
+    player::Player, protocol::Command, Frame, My9221LedMatrixError, Playback,
+};
+
+#[test]
+fn refused_command_not_written() {
+    let mut player = Player::new(I2cMock::new(&[]), 0x65);
+
+    let command = Command::DisplayFlash {
+        playback: Playback::Forever,
+        from_idx: 3,
+        to_idx: 1,
+    };
+    assert!(matches!(
+        player.send(command),
+        Err(My9221LedMatrixError::InvalidArgument)
+    ));
+    assert!(player.is_idle());
+    assert_eq!(player.poll(0).unwrap(), None);
+
+    player.release().done();
+}
+
+#[test]
+fn refused_command_keeps_current_job() {
+    let command = Command::DisplayOff;
+    let mut player = Player::new(
+        I2cMock::new(&[I2cTransaction::write(0x65, vec![0x06])]),
+        0x65,
+    );
+
+    player.send(command).unwrap();
+    let refused = Command::DisplayCustom {
+        frame: Frame::new(),
+        index: 5,
+        frames_number: 5,
+        playback: Playback::Forever,
+    };
+    assert!(player.send(refused).is_err());
+    assert_eq!(player.poll(0).unwrap(), None);
+
+    player.release().done();
+}
@@ -0,0 +1,326 @@
+//! Wire encoding of the display commands, pinned to the bytes written by the
+//! driver before the commands were typed
+//!
+//! The forever flag is 1 to display forever for the bar, emoji, number and
+//! string, and 0 for the custom frames, the frames stored in flash and the
+//! built-in color animations.
+
+use grove_matrix_led_my9221_rs::{
+    protocol::{Command, DecodeError, Decoder, Text},
+    Emojis, Frame, Millis, Playback,
+};
+
+const ONCE: Playback = Playback::Once(Millis::new(0x1234));
+
+/// The bytes of a command written in a single packet
+fn encode(command: Command) -> Vec<u8> {
+    let mut packets = command.packets();
+    let bytes = packets.next().unwrap().as_bytes().to_vec();
+    assert!(packets.next().is_none());
+    assert_eq!(Command::decode(&bytes), Ok(command));
+    bytes
+}
+
+#[test]
+fn display_bar() {
+    let bar = |playback| Command::DisplayBar {
+        bar: 16,
+        playback,
+        color: 0x22,
+    };
+
+    assert_eq!(encode(bar(Playback::Forever)), [0x01, 16, 0, 0, 1, 0x22]);
+    assert_eq!(encode(bar(ONCE)), [0x01, 16, 0x34, 0x12, 0, 0x22]);
+}
+
+#[test]
+fn display_emoji() {
+    let emoji = |playback| Command::DisplayEmoji {
+        emoji: Emojis::Heart,
+        playback,
+    };
+
+    assert_eq!(encode(emoji(Playback::Forever)), [0x02, 0x0a, 0, 0, 1]);
+    assert_eq!(encode(emoji(ONCE)), [0x02, 0x0a, 0x34, 0x12, 0]);
+}
+
+#[test]
+fn display_number() {
+    let number = |playback| Command::DisplayNumber {
+        number: 0x0102,
+        playback,
+        color: 0x22,
+    };
+
+    assert_eq!(
+        encode(number(Playback::Forever)),
+        [0x03, 0x02, 0x01, 0, 0, 1, 0x22]
+    );
+    assert_eq!(
+        encode(number(ONCE)),
+        [0x03, 0x02, 0x01, 0x34, 0x12, 0, 0x22]
+    );
+}
+
+#[test]
+fn display_string() {
+    let string = |playback| Command::DisplayString {
+        text: Text::new("hi"),
+        playback,
+        color: 0x22,
+    };
+
+    assert_eq!(
+        encode(string(Playback::Forever)),
+        [0x04, 1, 0, 0, 2, 0x22, b'h', b'i']
+    );
+    assert_eq!(
+        encode(string(ONCE)),
+        [0x04, 0, 0x34, 0x12, 2, 0x22, b'h', b'i']
+    );
+}
+
+#[test]
+fn display_custom() {
+    let custom = |index, playback| Command::DisplayCustom {
+        frame: Frame::filled(0x22),
+        index,
+        frames_number: 2,
+        playback,
+    };
+    let bytes = |command: Command| {
+        command
+            .packets()
+            .flat_map(|packet| packet.as_bytes().to_vec())
+            .filter(|&byte| byte != 0x81)
+            .collect::<Vec<_>>()
+    };
+    let header = |bytes: Vec<u8>| {
+        assert!(bytes[8..].iter().all(|&byte| byte == 0x22));
+        bytes[..8].to_vec()
+    };
+
+    assert_eq!(
+        header(bytes(custom(0, Playback::Forever))),
+        [0x05, 0, 0, 0, 2, 0, 0, 0]
+    );
+    assert_eq!(
+        header(bytes(custom(0, ONCE))),
+        [0x05, 0x34, 0x12, 1, 2, 0, 0, 0]
+    );
+    // Only the first frame carries the playback
+    assert_eq!(header(bytes(custom(1, ONCE))), [0x05, 0, 0, 0, 2, 1, 0, 0]);
+}
+
+#[test]
+fn display_flash() {
+    let flash = |playback| Command::DisplayFlash {
+        playback,
+        from_idx: 1,
+        to_idx: 2,
+    };
+
+    assert_eq!(encode(flash(Playback::Forever)), [0x08, 0, 0, 0, 1, 2]);
+    assert_eq!(encode(flash(ONCE)), [0x08, 0x34, 0x12, 1, 1, 2]);
+}
+
+#[test]
+fn display_color_bar() {
+    let bar = |playback| Command::DisplayColorBar { bar: 16, playback };
+
+    assert_eq!(encode(bar(Playback::Forever)), [0x09, 16, 0, 0, 0]);
+    assert_eq!(encode(bar(ONCE)), [0x09, 16, 0x34, 0x12, 1]);
+}
+
+#[test]
+fn display_color_wave() {
+    let wave = |playback| Command::DisplayColorWave { wave: 3, playback };
+
+    assert_eq!(encode(wave(Playback::Forever)), [0x0a, 3, 0, 0, 0]);
+    assert_eq!(encode(wave(ONCE)), [0x0a, 3, 0x34, 0x12, 1]);
+}
+
+#[test]
+fn display_color_clockwise() {
+    let clockwise = |clockwise, big, playback| Command::DisplayColorClockwise {
+        clockwise,
+        big,
+        playback,
+    };
+
+    assert_eq!(
+        encode(clockwise(true, false, Playback::Forever)),
+        [0x0b, 0, 1, 0, 0, 0]
+    );
+    assert_eq!(
+        encode(clockwise(false, true, ONCE)),
+        [0x0b, 1, 0, 0x34, 0x12, 1]
+    );
+}
+
+#[test]
+fn display_color_animation() {
+    let animation = |playback| Command::DisplayColorAnimation {
+        from_idx: 1,
+        to_idx: 4,
+        playback,
+    };
+
+    assert_eq!(encode(animation(Playback::Forever)), [0x0c, 1, 4, 0, 0, 0]);
+    assert_eq!(encode(animation(ONCE)), [0x0c, 1, 4, 0x34, 0x12, 1]);
+}
+
+#[test]
+fn display_color_block() {
+    let block = |playback| Command::DisplayColorBlock {
+        rgb: 0x00ff8001,
+        playback,
+    };
+
+    assert_eq!(
+        encode(block(Playback::Forever)),
+        [0x0d, 0xff, 0x80, 0x01, 0, 0, 0]
+    );
+    assert_eq!(encode(block(ONCE)), [0x0d, 0xff, 0x80, 0x01, 0x34, 0x12, 1]);
+}
+
+#[test]
+fn display_string_packets() {
+    let lengths = |len| {
+        let text = "abcdefghijklmnopqrstuvwxyz01"[..len].to_string();
+        Command::DisplayString {
+            text: Text::new(&text),
+            playback: Playback::Forever,
+            color: 0x22,
+        }
+        .packets()
+        .map(|packet| (packet.as_bytes().len(), packet.delay_ms))
+        .collect::<Vec<_>>()
+    };
+
+    assert_eq!(lengths(25), [(31, 0)]);
+    // The continuation packet is padded to 6 bytes
+    assert_eq!(lengths(26), [(31, 1), (6, 0)]);
+    assert_eq!(lengths(28), [(31, 1), (6, 0)]);
+
+    let command = Command::DisplayString {
+        text: Text::new("abcdefghijklmnopqrstuvwxyz"),
+        playback: Playback::Forever,
+        color: 0x22,
+    };
+    let mut packets = command.packets();
+    let mut decoder = Decoder::new();
+    assert_eq!(decoder.feed(packets.next().unwrap().as_bytes()), Ok(None));
+    let last = packets.next().unwrap();
+    assert_eq!(last.as_bytes(), [0x81, b'z', 0, 0, 0, 0]);
+    // The padding is ignored when decoding
+    assert_eq!(decoder.feed(last.as_bytes()), Ok(Some(command)));
+}
+
+fn custom_frame() -> Command {
+    Command::DisplayCustom {
+        frame: Frame::filled(0x22),
+        index: 0,
+        frames_number: 1,
+        playback: Playback::Forever,
+    }
+}
+
+#[test]
+fn decoder_truncated_continuation() {
+    let command = custom_frame();
+    let packets: Vec<_> = command
+        .packets()
+        .map(|packet| packet.as_bytes().to_vec())
+        .collect();
+    let mut decoder = Decoder::new();
+
+    assert_eq!(decoder.feed(&packets[0]), Ok(None));
+    // The command waits for the missing bytes
+    assert_eq!(decoder.feed(&packets[1][..10]), Ok(None));
+    assert!(decoder.is_pending());
+    // and is dropped when another command starts
+    assert_eq!(decoder.feed(&[0x06]), Ok(Some(Command::DisplayOff)));
+    assert!(!decoder.is_pending());
+    assert_eq!(
+        decoder.feed(&packets[1]),
+        Err(DecodeError::UnexpectedContinuation)
+    );
+}
+
+#[test]
+fn decoder_unexpected_continuation() {
+    let mut decoder = Decoder::new();
+
+    assert_eq!(
+        decoder.feed(&[0x81, b'a']),
+        Err(DecodeError::UnexpectedContinuation)
+    );
+    // Once a command is complete
+    assert_eq!(decoder.feed(&[0xb0]), Ok(Some(Command::LedFlashOn)));
+    assert_eq!(
+        decoder.feed(&[0x81, b'a']),
+        Err(DecodeError::UnexpectedContinuation)
+    );
+}
+
+#[test]
+fn decoder_unknown_opcode() {
+    let mut decoder = Decoder::new();
+
+    for opcode in [0x07, 0x0e, 0x7f, 0xff] {
+        assert_eq!(
+            decoder.feed(&[opcode, 0, 0]),
+            Err(DecodeError::UnknownOpcode(opcode))
+        );
+        assert!(!decoder.is_pending());
+    }
+    assert_eq!(decoder.feed(&[]), Err(DecodeError::Empty));
+}
+
+#[test]
+fn decoder_custom_frame_too_long() {
+    let command = custom_frame();
+    let bytes: Vec<u8> = command
+        .packets()
+        .flat_map(|packet| packet.as_bytes().to_vec())
+        .filter(|&byte| byte != 0x81)
+        .collect();
+    assert_eq!(bytes.len(), 72);
+    let mut decoder = Decoder::new();
+
+    // A single packet can't hold more than the command
+    let mut long = bytes.clone();
+    long.push(0x22);
+    assert_eq!(
+        decoder.feed(&long),
+        Err(DecodeError::InvalidLength {
+            opcode: 0x05,
+            len: 73
+        })
+    );
+    assert!(!decoder.is_pending());
+
+    // Continuation data past the 72 bytes of the command is ignored
+    let (first, rest) = bytes.split_at(31);
+    assert_eq!(decoder.feed(first), Ok(None));
+    let mut continuation = vec![0x81];
+    continuation.extend_from_slice(rest);
+    continuation.extend_from_slice(&[0xff; 4]);
+    assert_eq!(decoder.feed(&continuation), Ok(Some(command)));
+}
+
+#[test]
+fn decoder_oversized_packet() {
+    let mut decoder = Decoder::new();
+
+    for opcode in [0x06, 0x01, 0x07, 0xff] {
+        assert_eq!(
+            decoder.feed(&[opcode; 100]),
+            Err(DecodeError::InvalidLength { opcode, len: 100 })
+        );
+        assert!(!decoder.is_pending());
+    }
+    // The decoder is still usable afterwards
+    assert_eq!(decoder.feed(&[0x06]), Ok(Some(Command::DisplayOff)));
+}
@@ -0,0 +1,310 @@
+//! Verification and rollback of an address change
+
+mod common;
+
+use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource};
+use embedded_hal_mock::eh1::{
+    delay::NoopDelay,
+    i2c::{Mock as I2cMock, Transaction as I2cTransaction},
+};
+use grove_matrix_led_my9221_rs::{My9221LedMatrixDevice, My9221LedMatrixError};
+
+const NACK: ErrorKind = ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address);
+
+/// Nothing answers at the address yet
+fn free(address: u8) -> I2cTransaction {
+    I2cTransaction::read(address, vec![0x00]).with_error(NACK)
+}
+
+fn set_address(from: u8, to: u8) -> I2cTransaction {
+    I2cTransaction::write(from, vec![0xc0, to])
+}
+
+fn reset_address(address: u8) -> I2cTransaction {
+    I2cTransaction::write(address, vec![0xc1])
+}
+
+/// A matrix answers the identification
+fn matrix(address: u8) -> Vec<I2cTransaction> {
+    vec![
+        I2cTransaction::write_read(address, vec![0x00], vec![0x86]),
+        I2cTransaction::write_read(address, vec![0xe2], vec![0x00, 0x01, 0x00, 0x00]),
+    ]
+}
+
+/// The identification fails with `error`
+fn unanswered(address: u8, error: ErrorKind) -> Vec<I2cTransaction> {
+    vec![I2cTransaction::write_read(address, vec![0x00], vec![0x00]).with_error(error)]
+}
+
+fn readdress(
+    expectations: &[Vec<I2cTransaction>],
+    from: u8,
+    to: u8,
+) -> (Result<(), My9221LedMatrixError<ErrorKind>>, u8) {
+    let i2c = I2cMock::new(&expectations.concat());
+    let mut led_matrix = My9221LedMatrixDevice::new(i2c, NoopDelay::new(), from);
+
+    let result = led_matrix.readdress(to);
+    let address = led_matrix.address();
+
+    let (mut i2c, _) = led_matrix.release();
+    i2c.done();
+    (result, address)
+}
+
+#[test]
+fn new_address_not_acknowledged_command_ignored() {
+    let (result, address) = readdress(
+        &[
+            vec![free(0x66), set_address(0x65, 0x66)],
+            unanswered(0x66, NACK),
+            // The device still answers at its previous address
+            matrix(0x65),
+        ],
+        0x65,
+        0x66,
+    );
+
+    assert!(matches!(
+        result,
+        Err(My9221LedMatrixError::AddressNotVerified {
+            address: 0x66,
+            rollback: Ok(Some(0x65)),
+        })
+    ));
+    assert_eq!(address, 0x65);
+}
+
+#[test]
+fn new_address_not_acknowledged_reset_to_default() {
+    let (result, address) = readdress(
+        &[
+            vec![free(0x21), set_address(0x20, 0x21)],
+            unanswered(0x21, NACK),
+            unanswered(0x20, NACK),
+            // Only the device acknowledges one of the resets
+            vec![reset_address(0x21), reset_address(0x20).with_error(NACK)],
+            matrix(0x65),
+        ],
+        0x20,
+        0x21,
+    );
+
+    assert!(matches!(
+        result,
+        Err(My9221LedMatrixError::AddressNotVerified {
+            address: 0x21,
+            rollback: Ok(Some(0x65)),
+        })
+    ));
+    assert_eq!(address, 0x65);
+}
+
+#[test]
+fn new_address_bus_error_answering_nowhere() {
+    let (result, address) = readdress(
+        &[
+            vec![free(0x21), set_address(0x20, 0x21)],
+            // A bus error doesn't skip the rollback
+            unanswered(0x21, ErrorKind::Bus),
+            unanswered(0x20, NACK),
+            vec![
+                reset_address(0x21).with_error(NACK),
+                reset_address(0x20).with_error(NACK),
+            ],
+            unanswered(0x65, NACK),
+        ],
+        0x20,
+        0x21,
+    );
+
+    assert!(matches!(
+        result,
+        Err(My9221LedMatrixError::AddressNotVerified {
+            address: 0x21,
+            rollback: Ok(None),
+        })
+    ));
+    assert_eq!(address, 0x20);
+}
+
+#[test]
+fn failed_rollback() {
+    let (result, address) = readdress(
+        &[
+            vec![free(0x21), set_address(0x20, 0x21)],
+            unanswered(0x21, NACK),
+            unanswered(0x20, ErrorKind::Bus),
+            // Every step of the rollback is tried
+            vec![
+                reset_address(0x21).with_error(ErrorKind::ArbitrationLoss),
+                reset_address(0x20).with_error(NACK),
+            ],
+            unanswered(0x65, NACK),
+        ],
+        0x20,
+        0x21,
+    );
+
+    // The first error is returned
+    assert!(matches!(
+        result,
+        Err(My9221LedMatrixError::AddressNotVerified {
+            address: 0x21,
+            rollback: Err(ErrorKind::Bus),
+        })
+    ));
+    assert_eq!(address, 0x20);
+}
+
+#[test]
+fn failed_set_address_verified() {
+    // The device received the command although the write failed
+    let (result, address) = readdress(
+        &[
+            vec![
+                free(0x66),
+                set_address(0x65, 0x66).with_error(ErrorKind::Bus),
+            ],
+            matrix(0x66),
+        ],
+        0x65,
+        0x66,
+    );
+
+    assert!(result.is_ok());
+    assert_eq!(address, 0x66);
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn async_failed_rollback() {
+    use common::block_on;
+    use grove_matrix_led_my9221_rs::My9221LedMatrixAsync;
+
+    let mut i2c = I2cMock::new(
+        &[
+            vec![free(0x66), set_address(0x65, 0x66)],
+            unanswered(0x66, NACK),
+            unanswered(0x65, ErrorKind::Bus),
+            vec![
+                reset_address(0x66).with_error(NACK),
+                reset_address(0x65).with_error(ErrorKind::ArbitrationLoss),
+            ],
+            unanswered(0x65, NACK),
+        ]
+        .concat(),
+    );
+    let mut led_matrix = My9221LedMatrixAsync::default();
+
+    let result = block_on(led_matrix.readdress(&mut i2c, &mut NoopDelay::new(), 0x66));
+
+    assert!(matches!(
+        result,
+        Err(My9221LedMatrixError::AddressNotVerified {
+            address: 0x66,
+            rollback: Err(ErrorKind::Bus),
+        })
+    ));
+    assert_eq!(led_matrix.address(), 0x65);
+    i2c.done();
+}
+
+/// The error of a bus carrying more than its kind
+#[derive(Debug, PartialEq)]
+struct BusError {
+    kind: ErrorKind,
+    transaction: usize,
+}
+
+impl embedded_hal::i2c::Error for BusError {
+    fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+}
+
+/// A bus numbering its transactions, which fail as mocked
+struct NumberedBus {
+    i2c: I2cMock,
+    transactions: usize,
+}
+
+impl embedded_hal::i2c::ErrorType for NumberedBus {
+    type Error = BusError;
+}
+
+impl NumberedBus {
+    fn numbered(&mut self, result: Result<(), ErrorKind>) -> Result<(), BusError> {
+        self.transactions += 1;
+        result.map_err(|kind| BusError {
+            kind,
+            transaction: self.transactions,
+        })
+    }
+}
+
+impl embedded_hal::i2c::I2c for NumberedBus {
+    fn read(&mut self, address: u8, read: &mut [u8]) -> Result<(), BusError> {
+        let result = self.i2c.read(address, read);
+        self.numbered(result)
+    }
+
+    fn write(&mut self, address: u8, write: &[u8]) -> Result<(), BusError> {
+        let result = self.i2c.write(address, write);
+        self.numbered(result)
+    }
+
+    fn write_read(&mut self, address: u8, write: &[u8], read: &mut [u8]) -> Result<(), BusError> {
+        let result = self.i2c.write_read(address, write, read);
+        self.numbered(result)
+    }
+
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [embedded_hal::i2c::Operation<'_>],
+    ) -> Result<(), BusError> {
+        let result = self.i2c.transaction(address, operations);
+        self.numbered(result)
+    }
+}
+
+#[test]
+fn failed_rollback_bus_error() {
+    let i2c = I2cMock::new(
+        &[
+            vec![free(0x21), set_address(0x20, 0x21)],
+            unanswered(0x21, NACK),
+            unanswered(0x20, NACK),
+            vec![
+                reset_address(0x21).with_error(ErrorKind::Bus),
+                reset_address(0x20).with_error(NACK),
+            ],
+            unanswered(0x65, NACK),
+        ]
+        .concat(),
+    );
+    let bus = NumberedBus {
+        i2c,
+        transactions: 0,
+    };
+    let mut led_matrix = My9221LedMatrixDevice::new(bus, NoopDelay::new(), 0x20);
+
+    // The error of the bus itself is returned, not only its kind
+    let result = led_matrix.readdress(0x21);
+    let Err(My9221LedMatrixError::AddressNotVerified { address, rollback }) = result else {
+        panic!("{result:?}");
+    };
+    assert_eq!(address, 0x21);
+    assert_eq!(
+        rollback,
+        Err(BusError {
+            kind: ErrorKind::Bus,
+            transaction: 5,
+        })
+    );
+
+    let (mut bus, _) = led_matrix.release();
+    bus.i2c.done();
+}
@@ -0,0 +1,31 @@
+//! Images rendered from the frames
+
+use grove_matrix_led_my9221_rs::{render, Colors, Frame, Millis};
+
+/// The delays of the frames of a GIF, in hundredths of a second
+fn delays(durations: &[u16]) -> Vec<u16> {
+    let frame = Frame::filled(Colors::Red);
+    let mut gif = Vec::new();
+    render::write_gif(
+        &mut gif,
+        durations.iter().map(|&ms| (&frame, Millis::new(ms))),
+        1,
+    )
+    .unwrap();
+
+    let mut decoder = gif::DecodeOptions::new().read_info(gif.as_slice()).unwrap();
+    let mut delays = Vec::new();
+    while let Some(frame) = decoder.read_next_frame().unwrap() {
+        delays.push(frame.delay);
+    }
+    delays
+}
+
+#[test]
+fn gif_delays() {
+    assert_eq!(delays(&[500, 1000, 20]), [50, 100, 2]);
+    // Rounded down to 10 ms
+    assert_eq!(delays(&[15, 99, 65535]), [1, 9, 6553]);
+    // But never 0, which viewers play at their own speed
+    assert_eq!(delays(&[0, 1, 9, 10]), [1, 1, 1, 1]);
+}
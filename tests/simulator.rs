@@ -0,0 +1,298 @@
+//! The simulator fed with the bytes written by the driver before the commands
+//! were typed
+//!
+//! The simulator decodes the packets with the same
+//! [`Decoder`](grove_matrix_led_my9221_rs::protocol::Decoder) the commands
+//! are encoded with, so the packets are written out by hand here rather than
+//! encoded, to catch a change of the wire format on both sides.
+
+use embedded_hal::i2c::I2c;
+use grove_matrix_led_my9221_rs::{
+    simulator::{Content, Simulator},
+    DisplayRotate, Emojis, Frame, Millis, Playback,
+};
+
+const ONCE: Playback = Playback::Once(Millis::new(0x1234));
+
+/// The content displayed once `packets` are written
+fn display(packets: &[&[u8]]) -> Content {
+    let mut simulator = Simulator::default();
+    for packet in packets {
+        simulator.write(0x65, packet).unwrap();
+    }
+    simulator.content().clone()
+}
+
+/// The packets of a custom frame: a header of 8 bytes and the 64 leds,
+/// split in 3 packets of 24 bytes
+fn custom(header: [u8; 8], frame: &Frame) -> Vec<Vec<u8>> {
+    let mut buf = header.to_vec();
+    buf.extend(frame.data.iter().copied());
+    buf.chunks(24)
+        .enumerate()
+        .map(|(i, chunk)| {
+            let opcode = if i == 0 { None } else { Some(0x81) };
+            opcode.into_iter().chain(chunk.iter().copied()).collect()
+        })
+        .collect()
+}
+
+#[test]
+fn display_bar() {
+    assert_eq!(
+        display(&[&[0x01, 16, 0, 0, 1, 0x22]]),
+        Content::Bar {
+            bar: 16,
+            playback: Playback::Forever,
+            color: 0x22,
+        }
+    );
+    assert_eq!(
+        display(&[&[0x01, 16, 0x34, 0x12, 0, 0x22]]),
+        Content::Bar {
+            bar: 16,
+            playback: ONCE,
+            color: 0x22,
+        }
+    );
+}
+
+#[test]
+fn display_emoji() {
+    assert_eq!(
+        display(&[&[0x02, 0x0a, 0, 0, 1]]),
+        Content::Emoji {
+            emoji: Emojis::Heart,
+            playback: Playback::Forever,
+        }
+    );
+    assert_eq!(
+        display(&[&[0x02, 0x0a, 0x34, 0x12, 0]]),
+        Content::Emoji {
+            emoji: Emojis::Heart,
+            playback: ONCE,
+        }
+    );
+}
+
+#[test]
+fn display_number() {
+    assert_eq!(
+        display(&[&[0x03, 0x02, 0x01, 0x34, 0x12, 0, 0x22]]),
+        Content::Number {
+            number: 0x0102,
+            playback: ONCE,
+            color: 0x22,
+        }
+    );
+}
+
+#[test]
+fn display_string() {
+    assert_eq!(
+        display(&[&[0x04, 1, 0, 0, 2, 0x22, b'h', b'i']]),
+        Content::String {
+            string: "hi".into(),
+            playback: Playback::Forever,
+            color: 0x22,
+        }
+    );
+
+    // The last characters of a long string follow in a packet of 6 bytes
+    let mut first = vec![0x04, 0, 0x34, 0x12, 27, 0x22];
+    first.extend_from_slice(b"abcdefghijklmnopqrstuvwxy");
+    assert_eq!(
+        display(&[&first, &[0x81, b'z', b'0', 0, 0, 0]]),
+        Content::String {
+            string: "abcdefghijklmnopqrstuvwxyz0".into(),
+            playback: ONCE,
+            color: 0x22,
+        }
+    );
+}
+
+#[test]
+fn display_custom() {
+    let frames = [Frame::filled(0x22), Frame::filled(0x33)];
+    let mut simulator = Simulator::default();
+
+    // The last frame first, only the first one carries the playback
+    let packets = [
+        custom([0x05, 0, 0, 0, 2, 1, 0, 0], &frames[1]),
+        custom([0x05, 0x34, 0x12, 1, 2, 0, 0, 0], &frames[0]),
+    ];
+    for packet in packets.iter().flatten() {
+        simulator.write(0x65, packet).unwrap();
+    }
+
+    assert_eq!(simulator.frames(), frames);
+    assert_eq!(simulator.content(), &Content::Frames { playback: ONCE });
+
+    let packets = custom([0x05, 0, 0, 0, 1, 0, 0, 0], &frames[1]);
+    for packet in &packets {
+        simulator.write(0x65, packet).unwrap();
+    }
+    assert_eq!(simulator.frames(), &frames[1..]);
+    assert_eq!(
+        simulator.content(),
+        &Content::Frames {
+            playback: Playback::Forever,
+        }
+    );
+}
+
+#[test]
+fn display_flash() {
+    assert_eq!(
+        display(&[&[0x08, 0, 0, 0, 1, 2]]),
+        Content::Flash {
+            playback: Playback::Forever,
+            from_idx: 1,
+            to_idx: 2,
+        }
+    );
+    assert_eq!(
+        display(&[&[0x08, 0x34, 0x12, 1, 1, 2]]),
+        Content::Flash {
+            playback: ONCE,
+            from_idx: 1,
+            to_idx: 2,
+        }
+    );
+}
+
+#[test]
+fn display_color_animations() {
+    assert_eq!(
+        display(&[&[0x09, 16, 0, 0, 0]]),
+        Content::ColorBar {
+            bar: 16,
+            playback: Playback::Forever,
+        }
+    );
+    assert_eq!(
+        display(&[&[0x0a, 3, 0x34, 0x12, 1]]),
+        Content::ColorWave {
+            wave: 3,
+            playback: ONCE,
+        }
+    );
+    assert_eq!(
+        display(&[&[0x0b, 0, 1, 0, 0, 0]]),
+        Content::ColorClockwise {
+            clockwise: true,
+            big: false,
+            playback: Playback::Forever,
+        }
+    );
+    assert_eq!(
+        display(&[&[0x0c, 1, 4, 0x34, 0x12, 1]]),
+        Content::ColorAnimation {
+            from_idx: 1,
+            to_idx: 4,
+            playback: ONCE,
+        }
+    );
+    assert_eq!(
+        display(&[&[0x0d, 0xff, 0x80, 0x01, 0, 0, 0]]),
+        Content::ColorBlock {
+            rgb: 0x00ff8001,
+            playback: Playback::Forever,
+        }
+    );
+}
+
+#[test]
+fn display_off() {
+    assert_eq!(display(&[&[0x02, 0x0a, 0, 0, 1], &[0x06]]), Content::Off);
+}
+
+#[test]
+fn flash() {
+    let mut simulator = Simulator::default();
+    for packet in custom([0x05, 0, 0, 0, 1, 0, 0, 0], &Frame::filled(0x22)) {
+        simulator.write(0x65, &packet).unwrap();
+    }
+
+    simulator.write(0x65, &[0xa0]).unwrap();
+    assert_eq!(simulator.flash(), [Frame::filled(0x22)]);
+    simulator.write(0x65, &[0xa1]).unwrap();
+    assert!(simulator.flash().is_empty());
+}
+
+#[test]
+fn settings() {
+    let mut simulator = Simulator::default();
+
+    simulator.write(0x65, &[0xb0]).unwrap();
+    simulator.write(0x65, &[0xb2]).unwrap();
+    simulator.write(0x65, &[0xe0]).unwrap();
+    simulator.write(0x65, &[0xb4, 1]).unwrap();
+    simulator.write(0x65, &[0xb5, 2, 3]).unwrap();
+    assert!(simulator.led_flash());
+    assert!(simulator.auto_sleep());
+    assert!(simulator.test_mode());
+    assert_eq!(simulator.rotation(), DisplayRotate::Deg90);
+    assert_eq!(simulator.offset(), (2, 3));
+
+    simulator.write(0x65, &[0xb1]).unwrap();
+    simulator.write(0x65, &[0xb3]).unwrap();
+    simulator.write(0x65, &[0xe1]).unwrap();
+    assert!(!simulator.led_flash());
+    assert!(!simulator.auto_sleep());
+    assert!(!simulator.test_mode());
+}
+
+#[test]
+fn address() {
+    let mut simulator = Simulator::default();
+
+    simulator.write(0x65, &[0xc0, 0x21]).unwrap();
+    assert_eq!(simulator.address(), 0x21);
+    assert!(simulator.write(0x65, &[0x06]).is_err());
+    simulator.write(0x21, &[0xc1]).unwrap();
+    assert_eq!(simulator.address(), 0x65);
+}
+
+#[test]
+fn queries() {
+    let mut simulator = Simulator::default().with_version(0x00010203).with_uid(0x42);
+    let mut id = [0; 4];
+    let mut version = [0; 4];
+    let mut uid = [0; 1];
+
+    // The answer is read after the command is written
+    simulator.write(0x65, &[0x00]).unwrap();
+    simulator.read(0x65, &mut id).unwrap();
+    simulator.write(0x65, &[0xe2]).unwrap();
+    simulator.read(0x65, &mut version).unwrap();
+    simulator.write(0x65, &[0xf1]).unwrap();
+    simulator.read(0x65, &mut uid).unwrap();
+
+    assert_eq!(id, [0x86, 0x28, 0x05, 0x80]);
+    assert_eq!(version, [0x00, 0x01, 0x02, 0x03]);
+    assert_eq!(uid, [0x42]);
+}
+
+#[test]
+fn oversized_write_ignored() {
+    let mut simulator = Simulator::default();
+    simulator.write(0x65, &[0x02, 0x0a, 0, 0, 1]).unwrap();
+    simulator.write(0x65, &[0xb4, 1]).unwrap();
+
+    // Longer than any command, the firmware ignores it
+    simulator.write(0x65, &[0x06; 100]).unwrap();
+    simulator.write(0x65, &[0xb4; 100]).unwrap();
+
+    assert_eq!(
+        simulator.content(),
+        &Content::Emoji {
+            emoji: Emojis::Heart,
+            playback: Playback::Forever,
+        }
+    );
+    assert_eq!(simulator.rotation(), DisplayRotate::Deg90);
+    // The following commands are still executed
+    simulator.write(0x65, &[0x06]).unwrap();
+    assert_eq!(simulator.content(), &Content::Off);
+}
@@ -0,0 +1,62 @@
+//! Frames played in a terminal
+
+use grove_matrix_led_my9221_rs::{
+    terminal::{write_frame, TerminalPlayer},
+    Colors, DisplayRotate, Frame, Hue, Millis,
+};
+
+/// A frame with a single red led
+fn led(x: i32, y: i32) -> Frame {
+    let mut frame = Frame::new();
+    frame.set_pixel(x, y, Colors::Red);
+    frame
+}
+
+fn written(frame: &Frame) -> Vec<u8> {
+    let mut output = Vec::new();
+    write_frame(&mut output, frame).unwrap();
+    output
+}
+
+#[test]
+fn transform() {
+    let player = TerminalPlayer::new()
+        .with_rotation(DisplayRotate::Deg90)
+        .with_offset((0, 1));
+
+    // Rotated first, then moved down
+    assert_eq!(player.transform(&led(0, 0)), led(7, 1));
+    assert_eq!(player.transform(&led(3, 0)), led(7, 4));
+    // Moved out of the display
+    assert_eq!(player.transform(&led(7, 0)), Frame::new());
+    assert_eq!(
+        TerminalPlayer::new().transform(&led(2, 5)),
+        led(2, 5),
+        "no rotation nor offset"
+    );
+}
+
+#[test]
+fn play_steps_through_frames() {
+    let frames = [led(7, 7), led(6, 7), Frame::filled(Hue(0x55))];
+    let player = TerminalPlayer::new()
+        .with_rotation(DisplayRotate::Deg180)
+        .with_offset((1, 2));
+
+    let mut output = Vec::new();
+    player.play(&mut output, &frames, Millis::new(0)).unwrap();
+
+    // Each frame is drawn over the previous one, 4 lines up
+    let mut uncovered = Frame::filled(Hue(0x55));
+    uncovered.fill_rect(0, 0, 8, 2, Hue::BLACK);
+    uncovered.fill_rect(0, 0, 1, 8, Hue::BLACK);
+    let expected = [
+        written(&led(1, 2)),
+        b"\x1b[4A".to_vec(),
+        written(&led(2, 2)),
+        b"\x1b[4A".to_vec(),
+        written(&uncovered),
+    ]
+    .concat();
+    assert_eq!(output, expected);
+}
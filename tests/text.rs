@@ -0,0 +1,147 @@
+//! Glyphs of the bundled fonts and the frames of a rendered text
+
+use grove_matrix_led_my9221_rs::{
+    font::{FONT_8X8, FONT_8X8_PROPORTIONAL},
+    text::{Scroll, TextRenderer},
+    Frame, Hue,
+};
+
+const H: [u8; 8] = [0x33, 0x33, 0x33, 0x3f, 0x33, 0x33, 0x33, 0x00];
+const I: [u8; 8] = [0x0c, 0x00, 0x0e, 0x0c, 0x0c, 0x0c, 0x1e, 0x00];
+const BOX: [u8; 8] = [0x00, 0x3f, 0x21, 0x21, 0x21, 0x21, 0x3f, 0x00];
+
+/// "Hi", the 'H' in red and the 'i' in blue
+fn hi() -> [(char, Hue); 2] {
+    [('H', Hue(0x00)), ('i', Hue(0xaa))]
+}
+
+/// The frame of `glyph` drawn from the column `x`, on a black background
+fn drawn(glyph: [u8; 8], x: i32, hue: Hue) -> Frame {
+    let mut frame = Frame::new();
+    for (y, row) in glyph.iter().enumerate() {
+        for column in 0..8 {
+            if row & (1 << column) != 0 {
+                frame.set_pixel(x + column, y as i32, hue);
+            }
+        }
+    }
+    frame
+}
+
+/// The leds of both frames, those of `b` over those of `a`
+fn over(a: Frame, b: Frame) -> Frame {
+    Frame {
+        data: core::array::from_fn(|i| {
+            if b.data[i] == Hue::BLACK.0 {
+                a.data[i]
+            } else {
+                b.data[i]
+            }
+        }),
+    }
+}
+
+#[test]
+fn glyph_lookup() {
+    for font in [&FONT_8X8, &FONT_8X8_PROPORTIONAL] {
+        assert_eq!(font.glyph('H'), H);
+        assert_eq!(font.glyph('i'), I);
+        assert_eq!(font.glyph(' '), [0; 8]);
+        assert!(font.contains(' '));
+        assert!(font.contains('~'));
+        assert!(!font.contains('\u{7f}'));
+        assert!(!font.contains('\u{1f}'));
+    }
+}
+
+#[test]
+fn proportional_width() {
+    // Every column of a fixed width glyph is displayed
+    for c in ['H', 'i', ' ', 'é'] {
+        assert_eq!(FONT_8X8.columns(c), (0, 8));
+    }
+
+    // The lit columns followed by a blank one
+    assert_eq!(FONT_8X8_PROPORTIONAL.columns('H'), (0, 7));
+    assert_eq!(FONT_8X8_PROPORTIONAL.columns('i'), (1, 5));
+    assert_eq!(FONT_8X8_PROPORTIONAL.columns(' '), (0, 3));
+
+    let renderer = TextRenderer::new(&FONT_8X8_PROPORTIONAL);
+    assert_eq!(renderer.render_str("Hi", Hue(0x00)).len(), 7 + 5 + 8);
+    let renderer = TextRenderer::new(&FONT_8X8);
+    assert_eq!(renderer.render_str("Hi", Hue(0x00)).len(), 8 + 8 + 8);
+}
+
+#[test]
+fn unknown_char_box() {
+    for c in ['é', '\u{7f}', '\u{1f600}', '\0'] {
+        assert!(!FONT_8X8.contains(c));
+        assert_eq!(FONT_8X8.glyph(c), BOX, "{c:?}");
+        assert_eq!(FONT_8X8_PROPORTIONAL.columns(c), (0, 7));
+    }
+
+    let renderer = TextRenderer::new(&FONT_8X8).with_scroll(Scroll::Static);
+    let frames: Vec<_> = renderer.render_str("é", Hue(0x22)).collect();
+    assert_eq!(frames, [drawn(BOX, 1, Hue(0x22))]);
+}
+
+#[test]
+fn scroll_left_frames() {
+    let renderer = TextRenderer::new(&FONT_8X8_PROPORTIONAL);
+    let frames: Vec<_> = renderer.render(hi()).collect();
+
+    // The first column of the text enters at the right edge
+    assert_eq!(frames.len(), 20);
+    for (index, frame) in frames.iter().enumerate() {
+        let x = 7 - index as i32;
+        let expected = over(drawn(H, x, Hue(0x00)), drawn(I, x + 7 - 1, Hue(0xaa)));
+        assert_eq!(*frame, expected, "frame {index}");
+    }
+    assert_eq!(frames[19], Frame::new());
+}
+
+#[test]
+fn scroll_right_frames() {
+    let renderer = TextRenderer::new(&FONT_8X8_PROPORTIONAL).with_scroll(Scroll::Right);
+    let frames: Vec<_> = renderer.render(hi()).collect();
+    let left: Vec<_> = TextRenderer::new(&FONT_8X8_PROPORTIONAL)
+        .render(hi())
+        .collect();
+
+    // The frames of a left scroll backwards, the blank one last
+    assert_eq!(frames.len(), 20);
+    assert_eq!(
+        frames[..19],
+        left[..19].iter().rev().copied().collect::<Vec<_>>()
+    );
+    assert_eq!(frames[19], Frame::new());
+    // The last column of the text, blank, enters at the left edge, then
+    // the last lit column of 'i'
+    assert_eq!(frames[0], Frame::new());
+    assert_eq!(frames[1], drawn(I, -4, Hue(0xaa)));
+}
+
+#[test]
+fn centered_frames() {
+    let renderer = TextRenderer::new(&FONT_8X8_PROPORTIONAL)
+        .with_scroll(Scroll::Static)
+        .with_background(Hue(0x55));
+    let frames: Vec<_> = renderer.render(hi()).collect();
+
+    // The 6 lit columns of 'H' from the column 1, the 4 of 'i' from the
+    // column 2
+    let background = |frame| over(Frame::filled(Hue(0x55)), frame);
+    let h = background(drawn(H, 1, Hue(0x00)));
+    let i = background(drawn(I, 1, Hue(0xaa)));
+    assert_eq!(frames, [h, i]);
+
+    // Each character moves up to the same frame, then out at the top
+    let renderer = renderer.with_scroll(Scroll::Up);
+    let frames: Vec<_> = renderer.render(hi()).collect();
+    assert_eq!(frames.len(), 24);
+    assert_eq!(frames[0].get_pixel(1, 7), Some(Hue(0x00)));
+    assert_eq!(frames[0].get_pixel(1, 6), Some(Hue(0x55)));
+    assert_eq!(frames[7], h);
+    assert_eq!(frames[15], i);
+    assert_eq!(frames[23], Frame::filled(Hue(0x55)));
+}
@@ -0,0 +1,78 @@
+//! Checks of the commands written to the tiles of a display
+
+use embedded_hal_mock::eh1::{
+    delay::NoopDelay,
+    i2c::{Mock as I2cMock, Transaction as I2cTransaction},
+};
+use grove_matrix_led_my9221_rs::{
+    protocol::Command,
+    tiling::{Tile, TiledDisplay},
+    Frame, My9221LedMatrixError, Playback,
+};
+
+fn display() -> TiledDisplay<2> {
+    TiledDisplay::new([Tile::new(0x65, 0, 0), Tile::new(0x66, 1, 0)])
+}
+
+#[test]
+fn refused_images_not_written() {
+    let mut i2c = I2cMock::new(&[]);
+    let images = [[Frame::new(); 2]; 2];
+
+    for frames_number in [0, 3] {
+        assert!(matches!(
+            display().display_frames(
+                &mut i2c,
+                &mut NoopDelay::new(),
+                &images,
+                Playback::Forever,
+                frames_number,
+            ),
+            Err(My9221LedMatrixError::InvalidArgument)
+        ));
+    }
+
+    i2c.done();
+}
+
+#[test]
+fn frames_number_clamped() {
+    let images: Vec<_> = (0..6)
+        .map(|i| [Frame::filled(i), Frame::filled(i + 0x10)])
+        .collect();
+    // Only the first 5 images are written, the last one first
+    let expectations: Vec<_> = (0..5u8)
+        .rev()
+        .flat_map(|index| {
+            let command = |frame| Command::DisplayCustom {
+                frame,
+                index,
+                frames_number: 5,
+                playback: Playback::Forever,
+            };
+            let [left, right] = images[index as usize].map(command);
+            left.packets()
+                .zip(right.packets())
+                .flat_map(|(left, right)| {
+                    [
+                        I2cTransaction::write(0x65, left.as_bytes().to_vec()),
+                        I2cTransaction::write(0x66, right.as_bytes().to_vec()),
+                    ]
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    let mut i2c = I2cMock::new(&expectations);
+
+    display()
+        .display_frames(
+            &mut i2c,
+            &mut NoopDelay::new(),
+            &images,
+            Playback::Forever,
+            9,
+        )
+        .unwrap();
+
+    i2c.done();
+}